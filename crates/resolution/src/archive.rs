@@ -0,0 +1,310 @@
+use crate::{
+    Day, FiscalQuarter, FiscalYear, FixedTimeZone, FromMonotonic, HalfYear, IsoWeek, Millis,
+    Minutes, Monotonic, Month, Quarter, Seconds, StartDay, StartMonth, SubDateResolution, Week,
+    Year, Zoned,
+};
+use chrono::Utc;
+use core::{error, fmt, marker::PhantomData};
+use rkyv::{
+    bytecheck::{CheckBytes, Verify},
+    munge::munge,
+    primitive::{ArchivedI64, ArchivedU64},
+    rancor::{fail, Fallible, Source},
+    Archive, Deserialize, Place, Portable, Serialize,
+};
+
+/// `ArchiveTag` identifies a resolution within an archive, including any parameters such as the
+/// length of `Minutes<N>` or the start day of `Week<D>`, so that an archived period can't be read
+/// as a period of a different resolution.
+///
+/// Implementations outside this crate should use a kind of `0x1000` or greater.
+pub trait ArchiveTag {
+    fn archive_tag() -> u64;
+    /// The `ArchiveZone` of a `Zoned` resolution, or `0` for resolutions without a zone
+    fn archive_zone() -> u64 {
+        0
+    }
+}
+
+/// `ArchiveZone` identifies the time zone of a `Zoned` resolution within an archive, so that a
+/// period archived in one zone can't be read as a period in another.
+///
+/// The identifier must not be `0`. Implementations outside this crate should use `0x1000` or
+/// greater.
+pub trait ArchiveZone {
+    fn archive_zone() -> u64;
+}
+
+impl ArchiveZone for Utc {
+    fn archive_zone() -> u64 {
+        1
+    }
+}
+
+/// Build an `ArchiveTag` from the kind of resolution and its parameter
+pub const fn archive_tag(kind: u32, param: u32) -> u64 {
+    (kind as u64) << 32 | param as u64
+}
+
+// a `Zoned` period has the tag of its local resolution with this bit set, along with the
+// `ArchiveZone` of its zone
+const ZONED: u64 = 1 << 63;
+
+/// `ArchivedPeriod` is the archived form of a period of the resolution `P`, storing its
+/// monotonic index along with the tag and zone of `P`, which are checked when the archive is
+/// validated.
+#[derive(Portable, CheckBytes)]
+#[bytecheck(crate = rkyv::bytecheck, verify)]
+#[rkyv(crate = rkyv)]
+#[repr(C)]
+pub struct ArchivedPeriod<P> {
+    index: ArchivedI64,
+    tag: ArchivedU64,
+    zone: ArchivedU64,
+    resolution: PhantomData<P>,
+}
+
+impl<P> ArchivedPeriod<P> {
+    pub fn index(&self) -> i64 {
+        self.index.to_native()
+    }
+    pub fn get(&self) -> P
+    where
+        P: FromMonotonic,
+    {
+        P::from_monotonic(self.index())
+    }
+    fn resolve_from(period: &P, out: Place<Self>)
+    where
+        P: Monotonic + ArchiveTag,
+    {
+        munge!(let ArchivedPeriod { index, tag, zone, resolution: _ } = out);
+        index.write(ArchivedI64::from_native(period.to_monotonic()));
+        tag.write(ArchivedU64::from_native(P::archive_tag()));
+        zone.write(ArchivedU64::from_native(P::archive_zone()));
+    }
+}
+
+impl<P> fmt::Debug for ArchivedPeriod<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ArchivedPeriod")
+            .field("index", &self.index())
+            .field("tag", &self.tag.to_native())
+            .field("zone", &self.zone.to_native())
+            .finish()
+    }
+}
+
+// the tag and zone of a resolution
+#[derive(Debug)]
+struct TagMismatch {
+    expected: (u64, u64),
+    found: (u64, u64),
+}
+
+impl fmt::Display for TagMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Archived period has resolution tag {:#x} in zone {:#x} but {:#x} in zone {:#x} was expected",
+            self.found.0, self.found.1, self.expected.0, self.expected.1
+        )
+    }
+}
+
+impl error::Error for TagMismatch {}
+
+// SAFETY: `verify` only reads the fields of the already checked `ArchivedPeriod`
+unsafe impl<P, C> Verify<C> for ArchivedPeriod<P>
+where
+    P: ArchiveTag,
+    C: Fallible + ?Sized,
+    C::Error: Source,
+{
+    fn verify(&self, _: &mut C) -> Result<(), C::Error> {
+        let expected = (P::archive_tag(), P::archive_zone());
+        let found = (self.tag.to_native(), self.zone.to_native());
+        if expected != found {
+            fail!(TagMismatch { expected, found });
+        }
+        Ok(())
+    }
+}
+
+impl<P, D> Deserialize<P, D> for ArchivedPeriod<P>
+where
+    P: FromMonotonic,
+    D: Fallible + ?Sized,
+{
+    fn deserialize(&self, _: &mut D) -> Result<P, D::Error> {
+        Ok(self.get())
+    }
+}
+
+macro_rules! archive_period {
+    ([$($g:tt)*] $ty:ty, $tag:expr) => {
+        impl<$($g)*> ArchiveTag for $ty {
+            fn archive_tag() -> u64 {
+                $tag
+            }
+        }
+
+        archive_period!([$($g)*] $ty);
+    };
+    ([$($g:tt)*] $ty:ty) => {
+        impl<$($g)*> Archive for $ty {
+            type Archived = ArchivedPeriod<Self>;
+            type Resolver = ();
+            fn resolve(&self, _: Self::Resolver, out: Place<Self::Archived>) {
+                ArchivedPeriod::resolve_from(self, out)
+            }
+        }
+
+        impl<T: Fallible + ?Sized, $($g)*> Serialize<T> for $ty {
+            fn serialize(&self, _: &mut T) -> Result<Self::Resolver, T::Error> {
+                Ok(())
+            }
+        }
+    };
+}
+
+archive_period!([const N: u32] Minutes<N>, archive_tag(1, N));
+archive_period!([const N: u32] Seconds<N>, archive_tag(2, N));
+archive_period!([const N: u32] Millis<N>, archive_tag(3, N));
+archive_period!([] Day, archive_tag(4, 0));
+archive_period!(
+    [D: StartDay] Week<D>,
+    archive_tag(5, D::weekday().num_days_from_monday())
+);
+archive_period!([] IsoWeek, archive_tag(6, 0));
+archive_period!([] Month, archive_tag(7, 0));
+archive_period!([] Quarter, archive_tag(8, 0));
+archive_period!([] HalfYear, archive_tag(9, 0));
+archive_period!([] Year, archive_tag(10, 0));
+archive_period!(
+    [S: StartMonth] FiscalQuarter<S>,
+    archive_tag(11, S::month().number_from_month())
+);
+archive_period!(
+    [S: StartMonth] FiscalYear<S>,
+    archive_tag(12, S::month().number_from_month())
+);
+impl<R, Z> ArchiveTag for Zoned<R, Z>
+where
+    R: SubDateResolution<Params = ()> + ArchiveTag,
+    Z: FixedTimeZone + ArchiveZone,
+{
+    fn archive_tag() -> u64 {
+        R::archive_tag() | ZONED
+    }
+    fn archive_zone() -> u64 {
+        Z::archive_zone()
+    }
+}
+
+archive_period!(
+    [R: SubDateResolution<Params = ()> + ArchiveTag, Z: FixedTimeZone + ArchiveZone] Zoned<R, Z>
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::date;
+    use crate::{DateResolution, FiveMinute, July, Monday, Sunday, Sydney, TimeRange};
+    use rkyv::{rancor::Error, Archived};
+
+    fn check<P>(period: P, bytes: &[u8])
+    where
+        P: ArchiveTag + FromMonotonic + PartialEq + fmt::Debug,
+    {
+        let archived = rkyv::access::<ArchivedPeriod<P>, Error>(bytes).unwrap();
+        assert_eq!(archived.get(), period);
+        assert_eq!(rkyv::deserialize::<P, Error>(archived).unwrap(), period);
+    }
+
+    macro_rules! round_trip {
+        ($($period:expr),+ $(,)?) => {$(
+            let period = $period;
+            check(period, &rkyv::to_bytes::<Error>(&period).unwrap());
+        )+};
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let datetime = date(2024, 7, 15).and_hms_opt(10, 30, 0).unwrap().and_utc();
+        let day = date(2024, 7, 15);
+        round_trip!(
+            FiveMinute::from(datetime),
+            Minutes::<240>::from(datetime),
+            Seconds::<15>::from(datetime),
+            Millis::<250>::from(datetime),
+            Day::new(day),
+            Week::<Sunday>::from_date(day, ()),
+            IsoWeek::from_date(day, ()),
+            Month::from_date(day, ()),
+            Quarter::from_date(day, ()),
+            HalfYear::from_date(day, ()),
+            Year::new(2024),
+            FiscalQuarter::<July>::from_date(day, ()),
+            FiscalYear::<July>::from_date(day, ()),
+            Zoned::<FiveMinute, Utc>::from(datetime),
+        );
+    }
+
+    #[test]
+    fn test_tag() {
+        let week = Week::<Monday>::from_date(date(2024, 7, 15), ());
+        let bytes = rkyv::to_bytes::<Error>(&week).unwrap();
+        assert!(rkyv::access::<ArchivedPeriod<Week<Monday>>, Error>(&bytes).is_ok());
+        assert!(rkyv::access::<ArchivedPeriod<Week<Sunday>>, Error>(&bytes).is_err());
+        assert!(rkyv::from_bytes::<Week<Sunday>, Error>(&bytes).is_err());
+        assert!(rkyv::from_bytes::<Day, Error>(&bytes).is_err());
+
+        let bytes = rkyv::to_bytes::<Error>(&FiveMinute::from_monotonic(100)).unwrap();
+        assert!(rkyv::from_bytes::<Minutes<15>, Error>(&bytes).is_err());
+        assert!(rkyv::from_bytes::<Zoned<FiveMinute, Utc>, Error>(&bytes).is_err());
+    }
+
+    impl ArchiveZone for Sydney {
+        fn archive_zone() -> u64 {
+            0x1000
+        }
+    }
+
+    #[test]
+    fn test_zone() {
+        let datetime = date(2024, 7, 15).and_hms_opt(10, 30, 0).unwrap().and_utc();
+        let sydney = Zoned::<FiveMinute, Sydney>::from(datetime.with_timezone(&Sydney));
+        round_trip!(sydney);
+
+        let bytes = rkyv::to_bytes::<Error>(&sydney).unwrap();
+        assert!(rkyv::access::<ArchivedPeriod<Zoned<FiveMinute, Utc>>, Error>(&bytes).is_err());
+        assert!(rkyv::from_bytes::<Zoned<FiveMinute, Utc>, Error>(&bytes).is_err());
+        assert!(rkyv::from_bytes::<FiveMinute, Error>(&bytes).is_err());
+
+        let bytes = rkyv::to_bytes::<Error>(&Zoned::<FiveMinute, Utc>::from(datetime)).unwrap();
+        assert!(rkyv::from_bytes::<Zoned<FiveMinute, Sydney>, Error>(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_range() {
+        let month = |y| Month::from_date(date(y, 1, 1), ());
+        let range = TimeRange::new(month(2024), 12.try_into().unwrap());
+        let bytes = rkyv::to_bytes::<Error>(&range).unwrap();
+        let archived = rkyv::access::<Archived<TimeRange<Month>>, Error>(&bytes).unwrap();
+        assert_eq!(archived.start().get(), range.start());
+        assert_eq!(archived.len(), range.len());
+        assert_eq!(
+            rkyv::from_bytes::<TimeRange<Month>, Error>(&bytes).unwrap(),
+            range
+        );
+
+        let ranges = [range, TimeRange::new(month(2025), 6.try_into().unwrap())];
+        let bytes = rkyv::to_bytes::<Error>(&ranges).unwrap();
+        assert_eq!(
+            rkyv::from_bytes::<[TimeRange<Month>; 2], Error>(&bytes).unwrap(),
+            ranges
+        );
+        assert!(rkyv::from_bytes::<[TimeRange<Quarter>; 2], Error>(&bytes).is_err());
+    }
+}
@@ -0,0 +1,220 @@
+use crate::{BusinessCalendar, DateResolution, Day, Monotonic, TimeResolution};
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use core::{cmp, fmt, hash};
+
+// when moving further than this many business days, use the monotonic index
+// rather than stepping through each date
+const STEP_LIMIT: u64 = 64;
+
+fn base() -> chrono::NaiveDate {
+    chrono::NaiveDate::from_ymd_opt(0, 1, 1).expect("valid date")
+}
+
+/// `BusinessDay` is a `Day` which is a business day according to the calendar `C`.
+///
+/// The successor of a `BusinessDay` is the next business day, so iterating a `TimeRange<BusinessDay<C>>`
+/// skips weekends and holidays.
+#[derive(Clone, Copy)]
+pub struct BusinessDay<C: BusinessCalendar> {
+    date: NaiveDate,
+    calendar: C,
+}
+
+impl<C: BusinessCalendar> BusinessDay<C> {
+    /// Returns `None` if the `date` is not a business day in the `calendar`
+    pub fn new(date: NaiveDate, calendar: C) -> Option<Self> {
+        calendar
+            .calendar()
+            .is_business_day(date)
+            .then_some(BusinessDay { date, calendar })
+    }
+    /// The first business day on or after `date`
+    pub fn following(date: NaiveDate, calendar: C) -> Self {
+        let cal = calendar.calendar();
+        let mut date = date;
+        while !cal.is_business_day(date) {
+            date = date.succ_opt().expect("valid date");
+        }
+        BusinessDay { date, calendar }
+    }
+    /// The last business day on or before `date`
+    pub fn preceding(date: NaiveDate, calendar: C) -> Self {
+        let cal = calendar.calendar();
+        let mut date = date;
+        while !cal.is_business_day(date) {
+            date = date.pred_opt().expect("valid date");
+        }
+        BusinessDay { date, calendar }
+    }
+    /// Find the business day from its monotonic index in the `calendar`
+    pub fn from_monotonic(idx: i64, calendar: C) -> Self {
+        BusinessDay {
+            date: calendar.calendar().nth_business_day(base(), idx),
+            calendar,
+        }
+    }
+    pub fn day(&self) -> Day {
+        self.date.into()
+    }
+    pub fn calendar(&self) -> C {
+        self.calendar
+    }
+}
+
+impl<C: BusinessCalendar> TimeResolution for BusinessDay<C> {
+    fn succ_n(&self, n: u64) -> Self {
+        if n > STEP_LIMIT {
+            return BusinessDay::from_monotonic(
+                self.to_monotonic() + i64::try_from(n).unwrap(),
+                self.calendar,
+            );
+        }
+        (0..n).fold(*self, |day, _| {
+            BusinessDay::following(day.date.succ_opt().expect("valid date"), day.calendar)
+        })
+    }
+    fn pred_n(&self, n: u64) -> Self {
+        if n > STEP_LIMIT {
+            return BusinessDay::from_monotonic(
+                self.to_monotonic() - i64::try_from(n).unwrap(),
+                self.calendar,
+            );
+        }
+        (0..n).fold(*self, |day, _| {
+            BusinessDay::preceding(day.date.pred_opt().expect("valid date"), day.calendar)
+        })
+    }
+    fn start_datetime(&self) -> DateTime<Utc> {
+        self.date.and_time(NaiveTime::MIN).and_utc()
+    }
+    fn fmt_name(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("BusinessDay")
+    }
+}
+
+impl<C: BusinessCalendar> Monotonic for BusinessDay<C> {
+    fn to_monotonic(&self) -> i64 {
+        self.calendar
+            .calendar()
+            .business_days_since(base(), self.date)
+    }
+    fn between(&self, other: Self) -> i64 {
+        self.calendar
+            .calendar()
+            .business_days_since(self.date, other.date)
+    }
+}
+
+impl<C: BusinessCalendar> DateResolution for BusinessDay<C> {
+    fn start(&self) -> chrono::NaiveDate {
+        self.date
+    }
+
+    type Params = C;
+
+    fn params(&self) -> Self::Params {
+        self.calendar
+    }
+
+    /// As there is no business day containing a weekend or holiday, these dates map to
+    /// the following business day
+    fn from_date(date: NaiveDate, params: Self::Params) -> Self {
+        BusinessDay::following(date, params)
+    }
+}
+
+impl<C: BusinessCalendar> fmt::Display for BusinessDay<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.date)
+    }
+}
+
+impl<C: BusinessCalendar> fmt::Debug for BusinessDay<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BusinessDay")
+            .field("date", &self.date)
+            .finish()
+    }
+}
+
+impl<C: BusinessCalendar> PartialEq for BusinessDay<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.date == other.date
+    }
+}
+
+impl<C: BusinessCalendar> Eq for BusinessDay<C> {}
+
+impl<C: BusinessCalendar> Ord for BusinessDay<C> {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.date.cmp(&other.date)
+    }
+}
+
+impl<C: BusinessCalendar> PartialOrd for BusinessDay<C> {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<C: BusinessCalendar> hash::Hash for BusinessDay<C> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.date.hash(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::date;
+    use crate::{Calendar, DateResolutionExt, Month, TimeRange};
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_succ_pred() {
+        let cal = Calendar::saturday_sunday([date(2024, 1, 1), date(2024, 1, 26)]);
+
+        let fri = BusinessDay::new(date(2023, 12, 29), &cal).unwrap();
+        assert!(BusinessDay::new(date(2023, 12, 30), &cal).is_none());
+        assert_eq!(fri.succ().start(), date(2024, 1, 2));
+        assert_eq!(fri.succ().pred(), fri);
+        assert_eq!(fri.succ_n(19).start(), date(2024, 1, 29));
+        assert_eq!(fri.succ_n(19).pred_n(19), fri);
+        assert_eq!(fri.between(fri.succ_n(19)), 19);
+
+        // large jumps go via the monotonic index
+        assert_eq!(fri.succ_n(1000).pred_n(1000), fri);
+        assert_eq!(fri.succ_n(1000), (0..1000).fold(fri, |d, _| d.succ()),);
+        assert_eq!(fri.between(fri.succ_n(1000)), 1000);
+        assert_eq!(BusinessDay::from_monotonic(fri.to_monotonic(), &cal), fri);
+    }
+
+    #[test]
+    fn test_range() {
+        let cal = Calendar::saturday_sunday([date(2024, 1, 1), date(2024, 1, 26)]);
+
+        let mth = Month::from_parts(2024, chrono::Month::January).unwrap();
+        let business_days = mth.to_business_days(&cal).unwrap();
+
+        assert_eq!(business_days.len().get(), 21);
+        assert_eq!(business_days.start().start(), date(2024, 1, 2));
+        assert_eq!(business_days.end().start(), date(2024, 1, 31));
+        assert!(business_days.iter().all(|d| cal.is_business_day(d.start())));
+        assert_eq!(
+            business_days.iter().map(|d| d.start()).collect::<Vec<_>>(),
+            mth.rescale::<Day>()
+                .iter()
+                .map(|d| d.start())
+                .filter(|d| cal.is_business_day(*d))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            business_days.rescale::<Month>(),
+            TimeRange::from_bounds(mth, mth)
+        );
+
+        let weekend =
+            TimeRange::from_bounds(Day::new(date(2024, 1, 6)), Day::new(date(2024, 1, 7)));
+        assert!(weekend.to_business_days(&cal).is_none());
+    }
+}
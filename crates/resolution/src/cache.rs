@@ -0,0 +1,515 @@
+use crate::{Error, TimeRange, TimeRangeSet, TimeResolution};
+use alloc::{collections, fmt, vec::Vec};
+use chrono::{DateTime, Utc};
+use core::{mem, num, ops};
+
+/// How the `Cache` handles new data for a point which already holds different data
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CacheMode {
+    /// The new data replaces the existing data
+    #[default]
+    Overwrite,
+    /// The new data is refused, and `add` returns `Error::GotNonMatchingNewData`
+    Reject,
+    /// The new data replaces the existing data, which is kept in the history of the point
+    Versioned,
+}
+
+pub struct Cache<K: Ord + fmt::Debug + Copy, T: Send + fmt::Debug + Eq + Copy> {
+    // The actual data in the cache, along with when it was added
+    data: collections::BTreeMap<K, (DateTime<Utc>, T)>,
+    // The requests for data which has been cached
+    requests: collections::BTreeSet<K>,
+    // Data which has been replaced, oldest first. Only kept in `CacheMode::Versioned`
+    history: collections::BTreeMap<K, Vec<(DateTime<Utc>, T)>>,
+    mode: CacheMode,
+    capacity: Option<num::NonZeroUsize>,
+    // when each point was last used, and the reverse, so the least recently used
+    // point can be found when the cache is over capacity
+    last_used: collections::BTreeMap<K, u64>,
+    by_last_used: collections::BTreeMap<u64, K>,
+    tick: u64,
+}
+
+// merge a request into a set of requests, grouping contigious on the way
+fn missing_pieces<K: Ord + fmt::Debug + Copy>(
+    request: collections::BTreeSet<K>,
+    requests: &collections::BTreeSet<K>,
+) -> Vec<collections::BTreeSet<K>> {
+    let mut to_request = Vec::new();
+    let mut current_request = collections::BTreeSet::new();
+
+    // there is a fundamental assumption that `request` is contigious
+    // as long as `request` is contigious, each of the returned requests
+    // will also be contigious
+    // there is no need to worry about filling gaps to reduce the total number
+    // of requests - the consumer will handle this
+    for requested in request {
+        if !requests.contains(&requested) {
+            current_request.insert(requested);
+        } else if !current_request.is_empty() {
+            to_request.push(mem::take(&mut current_request));
+        }
+    }
+
+    if !current_request.is_empty() {
+        to_request.push(current_request);
+    }
+
+    to_request
+}
+
+// No concept of partial, becuse we will simply request the missing data, then ask the cache again.
+pub enum CacheResponse<K: Ord + fmt::Debug + Copy, T: Send + fmt::Debug + Eq + Copy> {
+    Hit(collections::BTreeMap<K, T>), // means the whole request as able to be replied, doesn't necessarily mean the whole range of data is filled
+    Miss(Vec<collections::BTreeSet<K>>), // will be a minimal reasonable set of time ranges to request from the provider
+}
+
+impl<K: Ord + fmt::Debug + Copy, T: Send + fmt::Debug + Eq + Copy> Cache<K, T> {
+    pub fn get(&self, request: collections::BTreeSet<K>) -> CacheResponse<K, T> {
+        if request.is_empty() {
+            CacheResponse::Hit(collections::BTreeMap::new())
+        } else if self.requests.is_superset(&request) {
+            // mustn't be empty othewise we would have returned out of the first arm of the `if`
+            let bounds = (
+                *request.iter().next().unwrap(),
+                *request.iter().next_back().unwrap(),
+            );
+            CacheResponse::Hit(
+                self.data
+                    .range(bounds.0..=bounds.1)
+                    .map(|(k, (_, v))| (*k, *v))
+                    .collect(),
+            )
+        } else {
+            CacheResponse::Miss(missing_pieces(request, &self.requests))
+        }
+    }
+    /// As `get`, but on a hit the requested points are marked as recently used, so that they
+    /// are the last to be evicted when the cache has a capacity
+    pub fn get_and_touch(&mut self, request: collections::BTreeSet<K>) -> CacheResponse<K, T> {
+        let response = self.get(request.clone());
+        if let CacheResponse::Hit(_) = response {
+            for point in request {
+                self.touch(point);
+            }
+        }
+        response
+    }
+    /// An unbounded cache which overwrites existing data
+    pub fn empty() -> Cache<K, T> {
+        Cache::new(CacheMode::Overwrite, None)
+    }
+    /// A cache with the given handling of conflicting data. When a `capacity` is given, the
+    /// least recently used points are evicted once more than `capacity` points are cached,
+    /// except for the points in the response just added.
+    pub fn new(mode: CacheMode, capacity: Option<num::NonZeroUsize>) -> Cache<K, T> {
+        Cache {
+            data: collections::BTreeMap::new(),
+            requests: collections::BTreeSet::new(),
+            history: collections::BTreeMap::new(),
+            mode,
+            capacity,
+            last_used: collections::BTreeMap::new(),
+            by_last_used: collections::BTreeMap::new(),
+            tick: 0,
+        }
+    }
+    pub fn mode(&self) -> CacheMode {
+        self.mode
+    }
+    pub fn capacity(&self) -> Option<num::NonZeroUsize> {
+        self.capacity
+    }
+    /// The number of points which have been requested or have data
+    pub fn len(&self) -> usize {
+        self.last_used.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.last_used.is_empty()
+    }
+    /// Add the response to a request, overwriting any existing data.
+    ///
+    /// This ignores the `CacheMode`, and the data is treated as always having been current by
+    /// `as_of` and `get_as_of`. Use `add_as_of` to detect conflicts or keep versions.
+    pub fn add(
+        &mut self,
+        request_range: collections::BTreeSet<K>,
+        data: collections::BTreeMap<K, T>,
+    ) {
+        self.insert(
+            request_range,
+            data,
+            DateTime::<Utc>::MIN_UTC,
+            CacheMode::Overwrite,
+        );
+    }
+    /// Add the response to a request, where the data was current `as_of` the given time.
+    ///
+    /// In `CacheMode::Reject`, if any point already has different data then nothing is added
+    /// and the first such point is returned in the error.
+    pub fn add_as_of(
+        &mut self,
+        request_range: collections::BTreeSet<K>,
+        data: collections::BTreeMap<K, T>,
+        as_of: DateTime<Utc>,
+    ) -> Result<(), Error> {
+        if self.mode == CacheMode::Reject {
+            let conflict = data.iter().find_map(|(point, new)| {
+                let (_, old) = self.data.get(point)?;
+                (old != new).then_some((point, old, new))
+            });
+            if let Some((point, old, new)) = conflict {
+                return Err(Error::GotNonMatchingNewData {
+                    point: crate::error_text(format_args!("{point:?}")),
+                    old: crate::error_text(format_args!("{old:?}")),
+                    new: crate::error_text(format_args!("{new:?}")),
+                });
+            }
+        }
+        self.insert(request_range, data, as_of, self.mode);
+        Ok(())
+    }
+    fn insert(
+        &mut self,
+        mut request_range: collections::BTreeSet<K>,
+        data: collections::BTreeMap<K, T>,
+        as_of: DateTime<Utc>,
+        mode: CacheMode,
+    ) {
+        let previous_tick = self.tick;
+        for point in &request_range {
+            self.touch(*point);
+        }
+        self.requests.append(&mut request_range);
+        for (point, datum) in data {
+            self.touch(point);
+            match self.data.insert(point, (as_of, datum)) {
+                Some(old) if old.1 == datum => {
+                    // keep the time the data was first seen
+                    self.data.insert(point, old);
+                }
+                Some(old) if mode == CacheMode::Versioned => {
+                    self.history.entry(point).or_default().push(old);
+                }
+                _ => {}
+            }
+        }
+
+        self.evict(previous_tick);
+    }
+    /// Remove the cached data and requests for the points in `range`, so that they are
+    /// requested again
+    pub fn invalidate(&mut self, range: impl ops::RangeBounds<K>) {
+        let points = self
+            .last_used
+            .range(range)
+            .map(|(k, _)| *k)
+            .collect::<Vec<_>>();
+        for point in points {
+            self.remove(point);
+        }
+    }
+    /// The time at which the current data for the `point` was added
+    pub fn as_of(&self, point: &K) -> Option<DateTime<Utc>> {
+        self.data.get(point).map(|(as_of, _)| *as_of)
+    }
+    /// The data which has been replaced at the `point`, oldest first, along with the time
+    /// each was added. This is always empty unless the cache is `CacheMode::Versioned`.
+    pub fn history(&self, point: &K) -> &[(DateTime<Utc>, T)] {
+        self.history
+            .get(point)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+    /// The data at the `point` as it was known at the time `at`
+    pub fn get_as_of(&self, point: &K, at: DateTime<Utc>) -> Option<T> {
+        self.history(point)
+            .iter()
+            .chain(self.data.get(point))
+            .filter(|(as_of, _)| *as_of <= at)
+            .last()
+            .map(|(_, datum)| *datum)
+    }
+
+    fn touch(&mut self, point: K) {
+        self.tick += 1;
+        if let Some(previous) = self.last_used.insert(point, self.tick) {
+            self.by_last_used.remove(&previous);
+        }
+        self.by_last_used.insert(self.tick, point);
+    }
+    fn remove(&mut self, point: K) {
+        if let Some(last_used) = self.last_used.remove(&point) {
+            self.by_last_used.remove(&last_used);
+        }
+        self.data.remove(&point);
+        self.requests.remove(&point);
+        self.history.remove(&point);
+    }
+    // points used after `previous_tick` are never evicted, so that a response larger than the
+    // capacity is kept whole, until the next response is added
+    fn evict(&mut self, previous_tick: u64) {
+        let Some(capacity) = self.capacity else {
+            return;
+        };
+        while self.last_used.len() > capacity.get() {
+            match self.by_last_used.first_key_value().map(|(t, p)| (*t, *p)) {
+                Some((last_used, point)) if last_used <= previous_tick => self.remove(point),
+                _ => break,
+            }
+        }
+    }
+}
+
+/// `RangeCache` is a `Cache` which tracks the requests it has served as `TimeRange`s.
+///
+/// Checking a request against the cache is proportional to the number of gaps in the cached
+/// requests rather than the number of periods requested, which matters for long ranges of
+/// short periods, such as a year of `FiveMinute`s.
+pub struct RangeCache<P: TimeResolution, T: Send + fmt::Debug + Eq + Copy> {
+    // The actual data in the cache
+    data: collections::BTreeMap<P, T>,
+    // The requests for data which has been cached
+    requests: TimeRangeSet<P>,
+}
+
+pub enum RangeCacheResponse<P: TimeResolution, T: Send + fmt::Debug + Eq + Copy> {
+    Hit(collections::BTreeMap<P, T>), // the data within the request, which may not cover every period
+    Miss(Vec<TimeRange<P>>), // the disjoint, non-adjacent parts of the request which are not cached
+}
+
+impl<P: TimeResolution, T: Send + fmt::Debug + Eq + Copy> RangeCache<P, T> {
+    pub fn empty() -> RangeCache<P, T> {
+        RangeCache {
+            data: collections::BTreeMap::new(),
+            requests: TimeRangeSet::empty(),
+        }
+    }
+    pub fn get(&self, request: TimeRange<P>) -> RangeCacheResponse<P, T> {
+        let missing = self.requests.complement(request);
+        if missing.is_empty() {
+            RangeCacheResponse::Hit(
+                self.data
+                    .range(request.start()..=request.end())
+                    .map(|(k, v)| (*k, *v))
+                    .collect(),
+            )
+        } else {
+            RangeCacheResponse::Miss(missing.ranges().to_vec())
+        }
+    }
+    /// The parts of the `request` which are already cached
+    pub fn covered(&self, request: TimeRange<P>) -> TimeRangeSet<P> {
+        self.requests.intersection(&TimeRangeSet::from(request))
+    }
+    /// Add the response to a request, overwriting any existing data
+    pub fn add(&mut self, request: TimeRange<P>, data: collections::BTreeMap<P, T>) {
+        self.requests.insert(request);
+        self.data.extend(data);
+    }
+    /// Remove the cached data and requests within the `range`, so that they are requested again
+    pub fn invalidate(&mut self, range: TimeRange<P>) {
+        self.requests.remove(range);
+        let mut after = self.data.split_off(&range.start());
+        let mut after_range = after.split_off(&range.end().succ());
+        self.data.append(&mut after_range);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 1, 1, hour, 0, 0).unwrap()
+    }
+
+    fn data(points: &[(i64, i64)]) -> collections::BTreeMap<i64, i64> {
+        points.iter().copied().collect()
+    }
+
+    fn hit(response: CacheResponse<i64, i64>) -> collections::BTreeMap<i64, i64> {
+        match response {
+            CacheResponse::Hit(data) => data,
+            CacheResponse::Miss(missing) => panic!("unexpected miss: {missing:?}"),
+        }
+    }
+
+    #[test]
+    fn test_missing_pieces() {
+        let pieces = missing_pieces(
+            collections::BTreeSet::from([1, 2, 3, 4, 5, 6, 7, 8, 9, 10]),
+            &collections::BTreeSet::from([2, 3, 7, 8]),
+        );
+        assert_eq!(
+            pieces,
+            Vec::from([
+                collections::BTreeSet::from([1]),
+                collections::BTreeSet::from([4, 5, 6]),
+                collections::BTreeSet::from([9, 10]),
+            ])
+        )
+    }
+
+    #[test]
+    fn test_conflicts() {
+        let mut cache = Cache::new(CacheMode::Reject, None);
+        cache
+            .add_as_of((1..=3).collect(), data(&[(1, 10), (2, 20)]), at(0))
+            .unwrap();
+        // the same data is not a conflict
+        cache
+            .add_as_of((2..=4).collect(), data(&[(2, 20), (4, 40)]), at(1))
+            .unwrap();
+        assert_eq!(cache.as_of(&2), Some(at(0)));
+
+        let err = cache
+            .add_as_of((4..=5).collect(), data(&[(5, 50), (4, 41)]), at(2))
+            .unwrap_err();
+        let Error::GotNonMatchingNewData { point, old, new } = &err else {
+            panic!("unexpected error {err:?}");
+        };
+        assert_eq!(
+            (point.as_str(), old.as_str(), new.as_str()),
+            ("4", "40", "41")
+        );
+        // nothing from the rejected response was added
+        assert!(matches!(
+            cache.get((4..=5).collect()),
+            CacheResponse::Miss(_)
+        ));
+        assert_eq!(
+            hit(cache.get((1..=4).collect())),
+            data(&[(1, 10), (2, 20), (4, 40)])
+        );
+    }
+
+    #[test]
+    fn test_versions() {
+        let mut cache = Cache::new(CacheMode::Versioned, None);
+        cache
+            .add_as_of((1..=2).collect(), data(&[(1, 10)]), at(1))
+            .unwrap();
+        cache
+            .add_as_of((1..=2).collect(), data(&[(1, 11)]), at(2))
+            .unwrap();
+        cache
+            .add_as_of((1..=2).collect(), data(&[(1, 12)]), at(3))
+            .unwrap();
+
+        assert_eq!(hit(cache.get((1..=2).collect())), data(&[(1, 12)]));
+        assert_eq!(cache.history(&1), [(at(1), 10), (at(2), 11)]);
+        assert_eq!(cache.get_as_of(&1, at(0)), None);
+        assert_eq!(cache.get_as_of(&1, at(2)), Some(11));
+        assert_eq!(cache.get_as_of(&1, at(5)), Some(12));
+        assert!(cache.history(&2).is_empty());
+
+        let mut overwrite = Cache::empty();
+        overwrite
+            .add_as_of((1..=1).collect(), data(&[(1, 10)]), at(1))
+            .unwrap();
+        overwrite
+            .add_as_of((1..=1).collect(), data(&[(1, 11)]), at(2))
+            .unwrap();
+        assert!(overwrite.history(&1).is_empty());
+        assert_eq!(overwrite.get_as_of(&1, at(1)), None);
+    }
+
+    #[test]
+    fn test_invalidate() {
+        let mut cache = Cache::empty();
+        cache.add((1..=10).collect(), (1..=10).map(|i| (i, i)).collect());
+        cache.invalidate(4..7);
+        assert_eq!(cache.len(), 7);
+        let CacheResponse::Miss(missing) = cache.get((1..=10).collect()) else {
+            panic!("expected a miss")
+        };
+        assert_eq!(missing, [(4..=6).collect()]);
+        assert_eq!(hit(cache.get((7..=10).collect())).len(), 4);
+    }
+
+    #[test]
+    fn test_eviction() {
+        let mut cache = Cache::new(CacheMode::Overwrite, num::NonZeroUsize::new(4));
+        cache.add((1..=3).collect(), data(&[(1, 10), (2, 20), (3, 30)]));
+        // using 1 means 2 is now the least recently used
+        hit(cache.get_and_touch((1..=1).collect()));
+        cache.add((4..=5).collect(), data(&[(4, 40), (5, 50)]));
+        assert_eq!(cache.len(), 4);
+        assert!(matches!(
+            cache.get((2..=2).collect()),
+            CacheResponse::Miss(_)
+        ));
+        assert_eq!(
+            hit(cache.get((3..=5).collect())),
+            data(&[(3, 30), (4, 40), (5, 50)])
+        );
+        assert_eq!(hit(cache.get((1..=1).collect())), data(&[(1, 10)]));
+    }
+
+    #[test]
+    fn test_eviction_larger_than_capacity() {
+        let mut cache = Cache::new(CacheMode::Overwrite, num::NonZeroUsize::new(2));
+        cache.add((1..=2).collect(), data(&[(1, 10), (2, 20)]));
+        cache.add((3..=5).collect(), data(&[(3, 30), (4, 40), (5, 50)]));
+        // the whole of the latest response is kept, even though it is over capacity
+        assert_eq!(cache.len(), 3);
+        assert_eq!(
+            hit(cache.get((3..=5).collect())),
+            data(&[(3, 30), (4, 40), (5, 50)])
+        );
+        assert!(matches!(
+            cache.get((1..=2).collect()),
+            CacheResponse::Miss(_)
+        ));
+        cache.add((6..=6).collect(), data(&[(6, 60)]));
+        assert_eq!(cache.len(), 2);
+        assert_eq!(hit(cache.get((5..=6).collect())), data(&[(5, 50), (6, 60)]));
+    }
+
+    #[test]
+    fn test_range_cache() {
+        use crate::{days, Day, FromMonotonic};
+
+        let values = |start, end| {
+            (start..=end)
+                .map(|i| (Day::from_monotonic(i), i))
+                .collect::<collections::BTreeMap<_, _>>()
+        };
+
+        let mut cache = RangeCache::empty();
+        let RangeCacheResponse::Miss(missing) = cache.get(days(0, 9)) else {
+            panic!("expected a miss")
+        };
+        assert_eq!(missing, [days(0, 9)]);
+
+        cache.add(days(0, 2), values(0, 2));
+        cache.add(days(5, 6), values(5, 6));
+        let RangeCacheResponse::Miss(missing) = cache.get(days(1, 9)) else {
+            panic!("expected a miss")
+        };
+        assert_eq!(missing, [days(3, 4), days(7, 9)]);
+        assert_eq!(cache.covered(days(1, 9)).ranges(), [days(1, 2), days(5, 6)]);
+
+        cache.add(days(3, 9), values(3, 8));
+        let RangeCacheResponse::Hit(data) = cache.get(days(1, 9)) else {
+            panic!("expected a hit")
+        };
+        // the request was served even though there is no data for day 9
+        assert_eq!(data, values(1, 8));
+
+        cache.invalidate(days(4, 5));
+        let RangeCacheResponse::Miss(missing) = cache.get(days(0, 9)) else {
+            panic!("expected a miss")
+        };
+        assert_eq!(missing, [days(4, 5)]);
+        cache.add(days(4, 5), collections::BTreeMap::new());
+        let RangeCacheResponse::Hit(data) = cache.get(days(0, 9)) else {
+            panic!("expected a hit")
+        };
+        assert_eq!(data.len(), 7);
+        assert!(!data.contains_key(&Day::from_monotonic(4)));
+    }
+}
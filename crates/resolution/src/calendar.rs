@@ -0,0 +1,237 @@
+use alloc::collections::BTreeSet;
+use chrono::{Datelike, NaiveDate, Weekday};
+use core::fmt;
+
+/// `Weekend` is the set of weekdays which are never business days in a `Calendar`
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Weekend(u8);
+
+impl Weekend {
+    pub const SATURDAY_SUNDAY: Weekend = Weekend(0b110_0000);
+    pub const FRIDAY_SATURDAY: Weekend = Weekend(0b011_0000);
+    pub const NONE: Weekend = Weekend(0);
+
+    pub fn new(days: impl IntoIterator<Item = Weekday>) -> Weekend {
+        days.into_iter().fold(Weekend::NONE, Weekend::with)
+    }
+    pub fn with(self, day: Weekday) -> Weekend {
+        Weekend(self.0 | (1 << day.num_days_from_monday()))
+    }
+    pub fn contains(&self, day: Weekday) -> bool {
+        self.0 & (1 << day.num_days_from_monday()) != 0
+    }
+    /// number of days in each week which are not part of the weekend
+    pub fn working_days(&self) -> u32 {
+        7 - self.0.count_ones()
+    }
+    pub fn iter(&self) -> impl Iterator<Item = Weekday> + '_ {
+        [
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+            Weekday::Sat,
+            Weekday::Sun,
+        ]
+        .into_iter()
+        .filter(|d| self.contains(*d))
+    }
+}
+
+impl fmt::Debug for Weekend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+/// `Calendar` stores the definition of which dates are business days, being a weekend
+/// definition plus an explicit set of holidays.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Calendar {
+    weekend: Weekend,
+    holidays: BTreeSet<NaiveDate>,
+}
+
+impl Calendar {
+    pub fn new(
+        weekend: Weekend,
+        holidays: impl IntoIterator<Item = NaiveDate>,
+    ) -> Result<Calendar, crate::Error> {
+        if weekend.working_days() == 0 {
+            return Err(crate::Error::NoBusinessDays);
+        }
+        Ok(Calendar {
+            weekend,
+            holidays: holidays.into_iter().collect(),
+        })
+    }
+    /// Create a calendar with a Saturday and Sunday weekend
+    pub fn saturday_sunday(holidays: impl IntoIterator<Item = NaiveDate>) -> Calendar {
+        Calendar {
+            weekend: Weekend::SATURDAY_SUNDAY,
+            holidays: holidays.into_iter().collect(),
+        }
+    }
+    pub fn weekend(&self) -> Weekend {
+        self.weekend
+    }
+    pub fn holidays(&self) -> &BTreeSet<NaiveDate> {
+        &self.holidays
+    }
+    pub fn add_holiday(&mut self, date: NaiveDate) -> bool {
+        self.holidays.insert(date)
+    }
+    pub fn is_weekend(&self, date: NaiveDate) -> bool {
+        self.weekend.contains(date.weekday())
+    }
+    pub fn is_holiday(&self, date: NaiveDate) -> bool {
+        self.holidays.contains(&date)
+    }
+    pub fn is_business_day(&self, date: NaiveDate) -> bool {
+        !self.is_weekend(date) && !self.is_holiday(date)
+    }
+
+    // number of days in `[from, to)` which don't fall on the weekend
+    fn weekdays_between(&self, from: NaiveDate, to: NaiveDate) -> i64 {
+        let days = (to - from).num_days();
+        let full_weeks = days.div_euclid(7);
+        let first_weekday = from.weekday();
+        let partial = (0..days.rem_euclid(7))
+            .filter(|i| {
+                let day = first_weekday.num_days_from_monday() as i64 + i;
+                // unwrap: always in range 0..7
+                let day = Weekday::try_from(u8::try_from(day.rem_euclid(7)).unwrap()).unwrap();
+                !self.weekend.contains(day)
+            })
+            .count();
+        full_weeks * i64::from(self.weekend.working_days()) + partial as i64
+    }
+
+    // number of business days in `[from, to)` where `from <= to`
+    fn business_days_between(&self, from: NaiveDate, to: NaiveDate) -> i64 {
+        let holidays = self
+            .holidays
+            .range(from..to)
+            .filter(|d| !self.is_weekend(**d))
+            .count();
+        self.weekdays_between(from, to) - holidays as i64
+    }
+
+    /// The number of business days between `base` and `date`, negative when `date` is
+    /// earlier than `base`.
+    pub(crate) fn business_days_since(&self, base: NaiveDate, date: NaiveDate) -> i64 {
+        if date >= base {
+            self.business_days_between(base, date)
+        } else {
+            -self.business_days_between(date, base)
+        }
+    }
+
+    /// The inverse of `business_days_since`, finding the business day which has exactly
+    /// `n` business days between it and `base`.
+    pub(crate) fn nth_business_day(&self, base: NaiveDate, n: i64) -> NaiveDate {
+        let working_days = i64::from(self.weekend.working_days());
+        // start from the date which would be correct if there were no holidays. As holidays can only
+        // reduce the count, moving by the deficit in weekdays never overshoots the target, so this
+        // converges after roughly one step per holiday encountered
+        let mut date = base + chrono::Duration::days(n.div_euclid(working_days) * 7);
+        loop {
+            let deficit = n - self.business_days_since(base, date);
+            if deficit == 0 {
+                break;
+            }
+            date = self.add_weekdays(date, deficit);
+        }
+        while !self.is_business_day(date) {
+            date = date.succ_opt().expect("valid date");
+        }
+        date
+    }
+
+    // move by `n` days which aren't part of the weekend, ignoring holidays
+    fn add_weekdays(&self, date: NaiveDate, n: i64) -> NaiveDate {
+        let working_days = i64::from(self.weekend.working_days());
+        let mut date = date + chrono::Duration::days(n / working_days * 7);
+        let mut remaining = n % working_days;
+        while remaining > 0 {
+            if !self.is_weekend(date) {
+                remaining -= 1;
+            }
+            date = date.succ_opt().expect("valid date");
+        }
+        while remaining < 0 {
+            date = date.pred_opt().expect("valid date");
+            if !self.is_weekend(date) {
+                remaining += 1;
+            }
+        }
+        date
+    }
+}
+
+/// `BusinessCalendar` provides the `Calendar` used by a `BusinessDay`.
+///
+/// This is implemented for `&Calendar`, and can also be implemented for zero-sized marker types
+/// that refer to a `Calendar` stored in a static.
+pub trait BusinessCalendar: Copy + fmt::Debug {
+    fn calendar(&self) -> &Calendar;
+}
+
+impl BusinessCalendar for &Calendar {
+    fn calendar(&self) -> &Calendar {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::date;
+
+    #[test]
+    fn test_weekend() {
+        let weekend = Weekend::new([Weekday::Sat, Weekday::Sun]);
+        assert_eq!(weekend, Weekend::SATURDAY_SUNDAY);
+        assert_eq!(weekend.working_days(), 5);
+        assert!(weekend.contains(Weekday::Sat));
+        assert!(!weekend.contains(Weekday::Fri));
+        assert!(Calendar::new(
+            Weekend::new(weekend.iter().chain([
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri,
+            ])),
+            []
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_business_days_since() {
+        let cal = Calendar::saturday_sunday([date(2024, 1, 1), date(2024, 1, 26)]);
+        let base = date(2023, 12, 29);
+
+        let mut count = 0;
+        for day in base.iter_days().take(400) {
+            assert_eq!(cal.business_days_since(base, day), count);
+            if cal.is_business_day(day) {
+                assert_eq!(cal.nth_business_day(base, count), day);
+                count += 1;
+            }
+        }
+
+        let mut count = 0;
+        let mut day = base;
+        for _ in 0..400 {
+            day = day.pred_opt().unwrap();
+            if cal.is_business_day(day) {
+                count -= 1;
+                assert_eq!(cal.nth_business_day(base, count), day);
+            }
+            assert_eq!(cal.business_days_since(base, day), count);
+        }
+    }
+}
@@ -1,10 +1,10 @@
-use crate::{DateResolution, Month, TimeResolution};
-use alloc::{
-    fmt, str,
-    string::{String, ToString},
-};
+use crate::{DateResolution, TimeResolution};
+#[cfg(feature = "serde")]
+use alloc::string::{String, ToString};
 use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, Utc};
+#[cfg(feature = "serde")]
 use core::result;
+use core::{fmt, str};
 #[cfg(feature = "serde")]
 use serde::de;
 
@@ -160,8 +160,8 @@ impl crate::TimeResolution for Day {
     fn start_datetime(&self) -> DateTime<Utc> {
         self.start().and_time(NaiveTime::MIN).and_utc()
     }
-    fn name(&self) -> String {
-        "Day".to_string()
+    fn fmt_name(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Day")
     }
 }
 
@@ -0,0 +1,179 @@
+use crate::{DateResolution, Day, TimeRange, TimeResolution};
+use chrono::{Datelike, NaiveDate};
+use rust_decimal::Decimal;
+
+/// `DayCount` is a convention for the fraction of a year between two dates, as used for
+/// interest accruals.
+///
+/// Fractions are between a start date which is included and an end date which is not, so
+/// the fraction for a `TimeRange<Day>` runs from its first day up to the day after its last.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DayCount {
+    /// Actual days divided by 365
+    Act365Fixed,
+    /// Actual days divided by 360
+    Act360,
+    /// Actual days in each calendar year divided by the number of days in that year
+    ActActIsda,
+    /// 30/360 Bond Basis: a start on the 31st is treated as the 30th, as is an end on the
+    /// 31st when the start is the 30th or 31st
+    Thirty360BondBasis,
+    /// 30/360 US: as for Bond Basis, but a start on the last day of February is treated as the
+    /// 30th, as is an end on the last day of February when the start is too
+    Thirty360Us,
+    /// 30E/360 (Eurobond Basis): any start or end on the 31st is treated as the 30th
+    ThirtyE360,
+    /// 30E/360 ISDA: any start or end on the last day of the month is treated as the 30th,
+    /// except an end on the last day of February which is the `termination` date
+    ThirtyE360Isda { termination: Day },
+}
+
+fn is_last_of_february(date: NaiveDate) -> bool {
+    date.month() == 2 && date.succ_opt().is_some_and(|d| d.month() == 3)
+}
+
+fn is_last_of_month(date: NaiveDate) -> bool {
+    date.succ_opt().is_some_and(|d| d.month() != date.month())
+}
+
+fn thirty_360(start: NaiveDate, end: NaiveDate, d1: u32, d2: u32) -> i64 {
+    360 * i64::from(end.year() - start.year())
+        + 30 * (i64::from(end.month()) - i64::from(start.month()))
+        + (i64::from(d2) - i64::from(d1))
+}
+
+impl DayCount {
+    /// The number of days between the dates according to the convention, which is the
+    /// numerator of the year fraction
+    pub fn days(&self, start: Day, end: Day) -> i64 {
+        let (start, end) = (start.start(), end.start());
+        let (d1, d2) = (start.day(), end.day());
+        match self {
+            DayCount::Act365Fixed | DayCount::Act360 | DayCount::ActActIsda => {
+                (end - start).num_days()
+            }
+            DayCount::Thirty360BondBasis => {
+                let d1 = d1.min(30);
+                let d2 = if d1 == 30 { d2.min(30) } else { d2 };
+                thirty_360(start, end, d1, d2)
+            }
+            DayCount::Thirty360Us => {
+                let feb_start = is_last_of_february(start);
+                let d2 = if feb_start && is_last_of_february(end) {
+                    30
+                } else {
+                    d2
+                };
+                let d1 = if feb_start { 30 } else { d1.min(30) };
+                let d2 = if d1 == 30 { d2.min(30) } else { d2 };
+                thirty_360(start, end, d1, d2)
+            }
+            DayCount::ThirtyE360 => thirty_360(start, end, d1.min(30), d2.min(30)),
+            DayCount::ThirtyE360Isda { termination } => {
+                let d1 = if is_last_of_month(start) { 30 } else { d1 };
+                let d2 =
+                    if is_last_of_month(end) && !(end == termination.start() && end.month() == 2) {
+                        30
+                    } else {
+                        d2
+                    };
+                thirty_360(start, end, d1, d2)
+            }
+        }
+    }
+
+    /// The fraction of a year from `start` up to `end`. This is negative when `end` is
+    /// before `start`.
+    pub fn year_fraction(&self, start: Day, end: Day) -> Decimal {
+        match self {
+            DayCount::Act365Fixed => Decimal::from(self.days(start, end)) / Decimal::from(365),
+            DayCount::ActActIsda if end < start => -self.year_fraction(end, start),
+            DayCount::ActActIsda => {
+                let (start, end) = (start.start(), end.start());
+                (start.year()..=end.year())
+                    .map(|year| {
+                        let first = NaiveDate::from_yo_opt(year, 1).expect("valid date");
+                        let next = NaiveDate::from_yo_opt(year + 1, 1).expect("valid date");
+                        let days = (end.min(next) - start.max(first)).num_days();
+                        Decimal::from(days) / Decimal::from((next - first).num_days())
+                    })
+                    .sum()
+            }
+            _ => Decimal::from(self.days(start, end)) / Decimal::from(360),
+        }
+    }
+
+    /// The fraction of a year covered by the days of the `range`
+    pub fn range_fraction(&self, range: &TimeRange<Day>) -> Decimal {
+        self.year_fraction(range.start(), range.end().succ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(y: i32, m: u32, d: u32) -> Day {
+        Day::new(NaiveDate::from_ymd_opt(y, m, d).unwrap())
+    }
+
+    #[test]
+    fn test_actual() {
+        let (start, end) = (day(2003, 11, 1), day(2004, 5, 1));
+        assert_eq!(DayCount::Act365Fixed.days(start, end), 182);
+        assert_eq!(
+            DayCount::Act365Fixed.year_fraction(start, end),
+            Decimal::from(182) / Decimal::from(365)
+        );
+        assert_eq!(
+            DayCount::Act360.year_fraction(start, end),
+            Decimal::from(182) / Decimal::from(360)
+        );
+        let act_act = DayCount::ActActIsda.year_fraction(start, end);
+        assert_eq!(
+            act_act,
+            Decimal::from(61) / Decimal::from(365) + Decimal::from(121) / Decimal::from(366)
+        );
+        assert_eq!(act_act.round_dp(10), Decimal::new(4977243806, 10));
+        assert_eq!(DayCount::ActActIsda.year_fraction(end, start), -act_act);
+
+        // a whole leap year
+        let range = TimeRange::from_bounds(day(2024, 1, 1), day(2024, 12, 31));
+        assert_eq!(DayCount::ActActIsda.range_fraction(&range), Decimal::ONE);
+        assert_eq!(
+            DayCount::Thirty360BondBasis.range_fraction(&range),
+            Decimal::ONE
+        );
+    }
+
+    #[test]
+    fn test_thirty_360() {
+        let termination = day(2008, 2, 29);
+        let days = |start, end| {
+            [
+                DayCount::Thirty360BondBasis,
+                DayCount::Thirty360Us,
+                DayCount::ThirtyE360,
+                DayCount::ThirtyE360Isda { termination },
+            ]
+            .map(|dc| dc.days(start, end))
+        };
+        assert_eq!(days(day(2007, 1, 15), day(2007, 1, 30)), [15, 15, 15, 15]);
+        assert_eq!(days(day(2007, 1, 15), day(2007, 2, 15)), [30, 30, 30, 30]);
+        assert_eq!(days(day(2007, 2, 28), day(2007, 3, 31)), [33, 30, 32, 30]);
+        assert_eq!(days(day(2007, 3, 31), day(2007, 4, 30)), [30, 30, 30, 30]);
+        assert_eq!(days(day(2007, 1, 15), day(2007, 1, 31)), [16, 16, 15, 15]);
+        assert_eq!(
+            days(day(2007, 8, 31), day(2008, 2, 29)),
+            [179, 179, 179, 179]
+        );
+        assert_eq!(
+            days(day(2007, 2, 28), day(2008, 2, 29)),
+            [361, 360, 361, 359]
+        );
+        assert_eq!(
+            DayCount::ThirtyE360.year_fraction(day(2007, 1, 15), day(2007, 7, 15)),
+            Decimal::new(5, 1)
+        );
+    }
+}
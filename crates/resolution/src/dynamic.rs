@@ -0,0 +1,816 @@
+use crate::{
+    millis, minutes, seconds, DateResolution, Day, FiscalQuarter, FiscalYear, FromMonotonic,
+    HalfYear, IsoWeek, Millis, Minutes, Monotonic, Month, Quarter, Seconds, StartDay, StartMonth,
+    TimeResolution, Week, Year,
+};
+#[cfg(feature = "serde")]
+use alloc::string::{String, ToString};
+use chrono::{DateTime, Utc};
+#[cfg(feature = "serde")]
+use core::result;
+use core::{cmp, num::NonZeroU32};
+use core::{fmt, str};
+#[cfg(feature = "serde")]
+use serde::de;
+
+/// `DynResolution` is a runtime representation of the resolutions implemented in this library,
+/// for when the resolution is chosen by configuration or user input rather than at compile time.
+///
+/// It is displayed and parsed in the same format as `TimeResolution::name` of the corresponding type,
+/// eg `Minutes[Length:5]` or `Week[StartDay:Monday]`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum DynResolution {
+    Millis(NonZeroU32),
+    Seconds(NonZeroU32),
+    Minutes(NonZeroU32),
+    Day,
+    Week(chrono::Weekday),
+    IsoWeek,
+    Month,
+    Quarter,
+    HalfYear,
+    Year,
+    FiscalQuarter(chrono::Month),
+    FiscalYear(chrono::Month),
+}
+
+/// `TypedResolution` links a `TimeResolution` type to its `DynResolution`
+pub trait TypedResolution: TimeResolution + FromMonotonic {
+    fn dyn_resolution() -> DynResolution;
+    /// The period of this resolution which contains the `datetime`
+    fn containing(datetime: DateTime<Utc>) -> Self {
+        Self::from_monotonic(Self::dyn_resolution().period(datetime).index())
+    }
+}
+
+impl<const N: u32> TypedResolution for Millis<N> {
+    fn dyn_resolution() -> DynResolution {
+        DynResolution::Millis(const { NonZeroU32::new(N).expect("Millis length must not be zero") })
+    }
+}
+impl<const N: u32> TypedResolution for Seconds<N> {
+    fn dyn_resolution() -> DynResolution {
+        DynResolution::Seconds(
+            const { NonZeroU32::new(N).expect("Seconds length must not be zero") },
+        )
+    }
+}
+impl<const N: u32> TypedResolution for Minutes<N> {
+    fn dyn_resolution() -> DynResolution {
+        DynResolution::Minutes(
+            const { NonZeroU32::new(N).expect("Minutes length must not be zero") },
+        )
+    }
+}
+impl TypedResolution for Day {
+    fn dyn_resolution() -> DynResolution {
+        DynResolution::Day
+    }
+}
+impl<D: StartDay> TypedResolution for Week<D> {
+    fn dyn_resolution() -> DynResolution {
+        DynResolution::Week(D::weekday())
+    }
+}
+impl TypedResolution for IsoWeek {
+    fn dyn_resolution() -> DynResolution {
+        DynResolution::IsoWeek
+    }
+}
+impl TypedResolution for Month {
+    fn dyn_resolution() -> DynResolution {
+        DynResolution::Month
+    }
+}
+impl TypedResolution for Quarter {
+    fn dyn_resolution() -> DynResolution {
+        DynResolution::Quarter
+    }
+}
+impl TypedResolution for HalfYear {
+    fn dyn_resolution() -> DynResolution {
+        DynResolution::HalfYear
+    }
+}
+impl TypedResolution for Year {
+    fn dyn_resolution() -> DynResolution {
+        DynResolution::Year
+    }
+}
+impl<S: StartMonth> TypedResolution for FiscalQuarter<S> {
+    fn dyn_resolution() -> DynResolution {
+        DynResolution::FiscalQuarter(S::month())
+    }
+}
+impl<S: StartMonth> TypedResolution for FiscalYear<S> {
+    fn dyn_resolution() -> DynResolution {
+        DynResolution::FiscalYear(S::month())
+    }
+}
+
+macro_rules! with_start_month {
+    ($month:expr, $s:ident => $body:expr) => {
+        match $month {
+            chrono::Month::January => {
+                type $s = crate::January;
+                $body
+            }
+            chrono::Month::February => {
+                type $s = crate::February;
+                $body
+            }
+            chrono::Month::March => {
+                type $s = crate::March;
+                $body
+            }
+            chrono::Month::April => {
+                type $s = crate::April;
+                $body
+            }
+            chrono::Month::May => {
+                type $s = crate::May;
+                $body
+            }
+            chrono::Month::June => {
+                type $s = crate::June;
+                $body
+            }
+            chrono::Month::July => {
+                type $s = crate::July;
+                $body
+            }
+            chrono::Month::August => {
+                type $s = crate::August;
+                $body
+            }
+            chrono::Month::September => {
+                type $s = crate::September;
+                $body
+            }
+            chrono::Month::October => {
+                type $s = crate::October;
+                $body
+            }
+            chrono::Month::November => {
+                type $s = crate::November;
+                $body
+            }
+            chrono::Month::December => {
+                type $s = crate::December;
+                $body
+            }
+        }
+    };
+}
+
+// evaluates `$body` with `$p` as a type alias for the typed resolution, for all
+// the resolutions that implement `DateResolution`, otherwise evaluates `$sub_date`
+macro_rules! with_date_resolution {
+    ($resolution:expr, $p:ident => $body:expr, _ => $sub_date:expr) => {
+        match $resolution {
+            DynResolution::Millis(_) | DynResolution::Seconds(_) | DynResolution::Minutes(_) => {
+                $sub_date
+            }
+            DynResolution::Day => {
+                type $p = Day;
+                $body
+            }
+            DynResolution::Week(wd) => match wd {
+                chrono::Weekday::Mon => {
+                    type $p = Week<crate::Monday>;
+                    $body
+                }
+                chrono::Weekday::Tue => {
+                    type $p = Week<crate::Tuesday>;
+                    $body
+                }
+                chrono::Weekday::Wed => {
+                    type $p = Week<crate::Wednesday>;
+                    $body
+                }
+                chrono::Weekday::Thu => {
+                    type $p = Week<crate::Thursday>;
+                    $body
+                }
+                chrono::Weekday::Fri => {
+                    type $p = Week<crate::Friday>;
+                    $body
+                }
+                chrono::Weekday::Sat => {
+                    type $p = Week<crate::Saturday>;
+                    $body
+                }
+                chrono::Weekday::Sun => {
+                    type $p = Week<crate::Sunday>;
+                    $body
+                }
+            },
+            DynResolution::IsoWeek => {
+                type $p = IsoWeek;
+                $body
+            }
+            DynResolution::Month => {
+                type $p = Month;
+                $body
+            }
+            DynResolution::Quarter => {
+                type $p = Quarter;
+                $body
+            }
+            DynResolution::HalfYear => {
+                type $p = HalfYear;
+                $body
+            }
+            DynResolution::Year => {
+                type $p = Year;
+                $body
+            }
+            DynResolution::FiscalQuarter(m) => {
+                with_start_month!(m, S => {
+                    type $p = FiscalQuarter<S>;
+                    $body
+                })
+            }
+            DynResolution::FiscalYear(m) => {
+                with_start_month!(m, S => {
+                    type $p = FiscalYear<S>;
+                    $body
+                })
+            }
+        }
+    };
+}
+
+impl DynResolution {
+    // the length in milliseconds, for the resolutions shorter than a day
+    fn sub_date_millis(&self) -> Option<i64> {
+        match self {
+            DynResolution::Millis(n) => Some(i64::from(n.get())),
+            DynResolution::Seconds(n) => Some(i64::from(n.get()) * 1_000),
+            DynResolution::Minutes(n) => Some(i64::from(n.get()) * 60_000),
+            _ => None,
+        }
+    }
+
+    // whether periods are displayed by their start alone, rather than as `<start> => <end>`
+    fn is_unit_length(&self) -> bool {
+        matches!(
+            self,
+            DynResolution::Millis(n) | DynResolution::Seconds(n) | DynResolution::Minutes(n)
+                if n.get() == 1
+        )
+    }
+
+    pub fn is_sub_date(&self) -> bool {
+        self.sub_date_millis().is_some()
+    }
+
+    /// The period of this resolution which contains the `datetime`
+    pub fn period(&self, datetime: DateTime<Utc>) -> DynPeriod {
+        let index = with_date_resolution!(
+            *self,
+            P => P::from_date(datetime.date_naive(), ()).to_monotonic(),
+            _ => datetime
+                .timestamp_millis()
+                .div_euclid(self.sub_date_millis().expect("sub date resolution"))
+        );
+        DynPeriod::new(*self, index)
+    }
+
+    // resolutions are ordered from shortest to longest, with resolutions of the same length
+    // ordered by variant so that the ordering is consistent with `Eq`
+    fn sort_key(&self) -> (u8, i64, u8) {
+        match self {
+            DynResolution::Millis(_) => (0, self.sub_date_millis().expect("sub date"), 0),
+            DynResolution::Seconds(_) => (0, self.sub_date_millis().expect("sub date"), 1),
+            DynResolution::Minutes(_) => (0, self.sub_date_millis().expect("sub date"), 2),
+            DynResolution::Day => (1, 0, 0),
+            DynResolution::Week(wd) => (2, i64::from(wd.num_days_from_monday()), 0),
+            DynResolution::IsoWeek => (3, 0, 0),
+            DynResolution::Month => (4, 0, 0),
+            DynResolution::FiscalQuarter(m) => (5, i64::from(m.number_from_month()), 0),
+            DynResolution::Quarter => (6, 0, 0),
+            DynResolution::HalfYear => (7, 0, 0),
+            DynResolution::FiscalYear(m) => (8, i64::from(m.number_from_month()), 0),
+            DynResolution::Year => (9, 0, 0),
+        }
+    }
+
+    fn format_period(&self, index: i64, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(length) = self.sub_date_millis() else {
+            return with_date_resolution!(
+                *self,
+                P => write!(f, "{}", P::from_monotonic(index)),
+                _ => unreachable!()
+            );
+        };
+        let format_datetime = match self {
+            DynResolution::Millis(_) => millis::format_datetime,
+            DynResolution::Seconds(_) => seconds::format_datetime,
+            _ => minutes::format_datetime,
+        };
+        let start =
+            DateTime::<Utc>::from_timestamp_millis(index * length).expect("valid timestamp");
+        format_datetime(start, f)?;
+        if !self.is_unit_length() {
+            f.write_str(" => ")?;
+            format_datetime(start + chrono::Duration::milliseconds(length), f)?;
+        }
+        Ok(())
+    }
+
+    // the typed parsers reject periods which start outside the dates `NaiveDate` can hold, so
+    // any index parsed here can be converted back to a period
+    pub(crate) fn parse_index(&self, s: &str) -> Result<i64, crate::Error> {
+        let Some(length) = self.sub_date_millis() else {
+            return with_date_resolution!(
+                *self,
+                P => Ok(s.parse::<P>()?.to_monotonic()),
+                _ => unreachable!()
+            );
+        };
+        let parse_datetime = |input| match self {
+            DynResolution::Millis(_) => seconds::parse_datetime(input, millis::FORMAT),
+            DynResolution::Seconds(_) => seconds::parse_datetime(input, seconds::FORMAT),
+            _ => minutes::parse_datetime(input),
+        };
+        let err = || crate::Error::ParseCustom {
+            ty_name: "DynPeriod",
+            input: crate::error_text(s),
+        };
+        let start = if self.is_unit_length() {
+            parse_datetime(s)?
+        } else {
+            let (start, end) = s.split_once(" => ").ok_or_else(err)?;
+            let start = parse_datetime(start)?;
+            if (parse_datetime(end)? - start).num_milliseconds() != length {
+                return Err(err());
+            }
+            start
+        };
+        let start = start.timestamp_millis();
+        if start.rem_euclid(length) != 0 {
+            return Err(err());
+        }
+        Ok(start.div_euclid(length))
+    }
+}
+
+impl Ord for DynResolution {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.sort_key().cmp(&other.sort_key())
+    }
+}
+
+impl PartialOrd for DynResolution {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for DynResolution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DynResolution::Millis(n) => write!(f, "Millis[Length:{n}]"),
+            DynResolution::Seconds(n) => write!(f, "Seconds[Length:{n}]"),
+            DynResolution::Minutes(n) => write!(f, "Minutes[Length:{n}]"),
+            DynResolution::Day => f.write_str("Day"),
+            DynResolution::Week(wd) => write!(f, "Week[StartDay:{}]", weekday_name(*wd)),
+            DynResolution::IsoWeek => f.write_str("IsoWeek"),
+            DynResolution::Month => f.write_str("Month"),
+            DynResolution::Quarter => f.write_str("Quarter"),
+            DynResolution::HalfYear => f.write_str("HalfYear"),
+            DynResolution::Year => f.write_str("Year"),
+            DynResolution::FiscalQuarter(m) => write!(f, "FiscalQuarter[StartMonth:{}]", m.name()),
+            DynResolution::FiscalYear(m) => write!(f, "FiscalYear[StartMonth:{}]", m.name()),
+        }
+    }
+}
+
+fn weekday_name(wd: chrono::Weekday) -> &'static str {
+    match wd {
+        chrono::Weekday::Mon => "Monday",
+        chrono::Weekday::Tue => "Tuesday",
+        chrono::Weekday::Wed => "Wednesday",
+        chrono::Weekday::Thu => "Thursday",
+        chrono::Weekday::Fri => "Friday",
+        chrono::Weekday::Sat => "Saturday",
+        chrono::Weekday::Sun => "Sunday",
+    }
+}
+
+impl str::FromStr for DynResolution {
+    type Err = crate::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || crate::Error::ParseCustom {
+            ty_name: "DynResolution",
+            input: crate::error_text(s),
+        };
+        let resolution = match s {
+            "Day" => DynResolution::Day,
+            "IsoWeek" => DynResolution::IsoWeek,
+            "Month" => DynResolution::Month,
+            "Quarter" => DynResolution::Quarter,
+            "HalfYear" => DynResolution::HalfYear,
+            "Year" => DynResolution::Year,
+            _ => {
+                let (name, param) = s
+                    .strip_suffix(']')
+                    .and_then(|s| s.split_once('['))
+                    .ok_or_else(err)?;
+                let (key, value) = param.split_once(':').ok_or_else(err)?;
+                match (name, key) {
+                    ("Millis" | "Seconds" | "Minutes", "Length") => {
+                        let length = value.parse().map_err(|e| {
+                            crate::Error::ParseIntDetailed(e, crate::error_text(value))
+                        })?;
+                        match name {
+                            "Millis" => DynResolution::Millis(length),
+                            "Seconds" => DynResolution::Seconds(length),
+                            _ => DynResolution::Minutes(length),
+                        }
+                    }
+                    ("Week", "StartDay") => DynResolution::Week(value.parse().map_err(|_| err())?),
+                    ("FiscalQuarter", "StartMonth") => {
+                        DynResolution::FiscalQuarter(value.parse().map_err(|_| err())?)
+                    }
+                    ("FiscalYear", "StartMonth") => {
+                        DynResolution::FiscalYear(value.parse().map_err(|_| err())?)
+                    }
+                    _ => return Err(err()),
+                }
+            }
+        };
+        Ok(resolution)
+    }
+}
+
+/// `DynPeriod` is a period of a `DynResolution`, stored as the monotonic index of the corresponding
+/// typed resolution.
+///
+/// It is displayed as the resolution followed by the period in the format of the typed resolution,
+/// eg `Month:Jan-2024` or `Minutes[Length:5]:2024-01-01 10:00 => 2024-01-01 10:05`.
+///
+/// As a `TimeResolution`, periods of different resolutions are ordered by resolution first, and
+/// `between` will panic if the resolutions differ.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct DynPeriod {
+    resolution: DynResolution,
+    index: i64,
+}
+
+impl DynPeriod {
+    pub fn new(resolution: DynResolution, index: i64) -> Self {
+        DynPeriod { resolution, index }
+    }
+    pub fn resolution(&self) -> DynResolution {
+        self.resolution
+    }
+    pub fn index(&self) -> i64 {
+        self.index
+    }
+    pub fn to_typed<P: TypedResolution>(&self) -> Result<P, crate::Error> {
+        if self.resolution == P::dyn_resolution() {
+            Ok(P::from_monotonic(self.index))
+        } else {
+            Err(crate::Error::UnexpectedResolution {
+                required: P::dyn_resolution(),
+                actual: self.resolution,
+            })
+        }
+    }
+}
+
+impl<P: TypedResolution> From<P> for DynPeriod {
+    fn from(value: P) -> DynPeriod {
+        DynPeriod::new(P::dyn_resolution(), value.to_monotonic())
+    }
+}
+
+macro_rules! try_from_dyn_period {
+    ($([$($generics:tt)*] $ty:ty;)*) => {
+        $(
+            impl<$($generics)*> TryFrom<DynPeriod> for $ty {
+                type Error = crate::Error;
+                fn try_from(value: DynPeriod) -> Result<Self, Self::Error> {
+                    value.to_typed()
+                }
+            }
+        )*
+    };
+}
+
+try_from_dyn_period!(
+    [const N: u32] Millis<N>;
+    [const N: u32] Seconds<N>;
+    [const N: u32] Minutes<N>;
+    [] Day;
+    [D: StartDay] Week<D>;
+    [] IsoWeek;
+    [] Month;
+    [] Quarter;
+    [] HalfYear;
+    [] Year;
+    [S: StartMonth] FiscalQuarter<S>;
+    [S: StartMonth] FiscalYear<S>;
+);
+
+impl TimeResolution for DynPeriod {
+    fn succ_n(&self, n: u64) -> Self {
+        DynPeriod::new(self.resolution, self.index + i64::try_from(n).unwrap())
+    }
+    fn pred_n(&self, n: u64) -> Self {
+        DynPeriod::new(self.resolution, self.index - i64::try_from(n).unwrap())
+    }
+    fn start_datetime(&self) -> DateTime<Utc> {
+        with_date_resolution!(
+            self.resolution,
+            P => P::from_monotonic(self.index).start_datetime(),
+            _ => DateTime::<Utc>::from_timestamp_millis(
+                self.index * self.resolution.sub_date_millis().expect("sub date resolution"),
+            )
+            .expect("valid timestamp")
+        )
+    }
+    fn fmt_name(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.resolution)
+    }
+}
+
+impl Monotonic for DynPeriod {
+    fn to_monotonic(&self) -> i64 {
+        self.index
+    }
+    fn between(&self, other: Self) -> i64 {
+        assert_eq!(
+            self.resolution, other.resolution,
+            "periods must have the same resolution"
+        );
+        other.index - self.index
+    }
+}
+
+impl fmt::Display for DynPeriod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:", self.resolution)?;
+        self.resolution.format_period(self.index, f)
+    }
+}
+
+impl str::FromStr for DynPeriod {
+    type Err = crate::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // the resolution ends at the first `:` which isn't within its brackets
+        let mut depth = 0;
+        let split = s.char_indices().find(|(_, c)| {
+            match c {
+                '[' => depth += 1,
+                ']' => depth -= 1,
+                _ => {}
+            }
+            *c == ':' && depth == 0
+        });
+        let (idx, _) = split.ok_or_else(|| crate::Error::ParseCustom {
+            ty_name: "DynPeriod",
+            input: crate::error_text(s),
+        })?;
+        let resolution = s[..idx].parse::<DynResolution>()?;
+        let index = resolution.parse_index(&s[idx + 1..])?;
+        Ok(DynPeriod::new(resolution, index))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> de::Deserialize<'de> for DynResolution {
+    fn deserialize<D>(deserializer: D) -> result::Result<DynResolution, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for DynResolution {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> de::Deserialize<'de> for DynPeriod {
+    fn deserialize<D>(deserializer: D) -> result::Result<DynPeriod, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for DynPeriod {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FiveMinute, July, Monday, Second, Sunday, TimeRange};
+    use alloc::{string::ToString, vec::Vec};
+    use chrono::NaiveDate;
+
+    fn datetime(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
+        NaiveDate::from_ymd_opt(y, m, d)
+            .unwrap()
+            .and_hms_opt(h, min, 0)
+            .unwrap()
+            .and_utc()
+    }
+
+    fn roundtrip<P>(period: P)
+    where
+        P: TypedResolution + TryFrom<DynPeriod, Error = crate::Error> + fmt::Display + fmt::Debug,
+    {
+        let dynamic = DynPeriod::from(period);
+        assert_eq!(
+            alloc::format!("{}", dynamic.display_name()),
+            alloc::format!("{}", period.display_name())
+        );
+        assert_eq!(dynamic.start_datetime(), period.start_datetime());
+        assert_eq!(
+            dynamic.succ().start_datetime(),
+            period.succ().start_datetime()
+        );
+        assert_eq!(
+            dynamic.to_string(),
+            alloc::format!("{}:{}", period.display_name(), period)
+        );
+        assert_eq!(dynamic.to_string().parse::<DynPeriod>().unwrap(), dynamic);
+        assert_eq!(
+            dynamic.resolution().period(period.start_datetime()),
+            dynamic
+        );
+        assert_eq!(P::try_from(dynamic).unwrap(), period);
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let dt = datetime(2024, 7, 15, 10, 30);
+        roundtrip(Millis::<250>::from(dt));
+        roundtrip(Second::from(dt));
+        roundtrip(Seconds::<4>::from(dt));
+        roundtrip(crate::Minute::from(dt));
+        roundtrip(FiveMinute::from(dt));
+        roundtrip(Minutes::<1440>::from(dt));
+        roundtrip(Day::new(dt.date_naive()));
+        roundtrip(Week::<Monday>::from(dt));
+        roundtrip(Week::<Sunday>::from(dt));
+        roundtrip(IsoWeek::from(dt));
+        roundtrip(Month::from(dt));
+        roundtrip(Quarter::from(dt.date_naive()));
+        roundtrip(HalfYear::from(dt));
+        roundtrip(Year::from(dt));
+        roundtrip(FiscalQuarter::<July>::from(dt.date_naive()));
+        roundtrip(FiscalYear::<July>::from(dt.date_naive()));
+
+        // before the epoch
+        roundtrip(FiveMinute::from(datetime(1969, 12, 31, 23, 55)));
+    }
+
+    #[test]
+    fn test_parse() {
+        let period = "Minutes[Length:5]:2024-07-15 10:30 => 2024-07-15 10:35"
+            .parse::<DynPeriod>()
+            .unwrap();
+        assert_eq!(period.resolution(), FiveMinute::dyn_resolution());
+        assert_eq!(period.start_datetime(), datetime(2024, 7, 15, 10, 30));
+
+        assert_eq!(
+            "Month:Jul-2024".parse::<DynPeriod>().unwrap(),
+            DynPeriod::from(Month::from(datetime(2024, 7, 1, 0, 0)))
+        );
+
+        // periods must align to the resolution
+        assert!("Minutes[Length:5]:2024-07-15 10:31 => 2024-07-15 10:36"
+            .parse::<DynPeriod>()
+            .is_err());
+        assert!("Minutes[Length:5]:2024-07-15 10:30 => 2024-07-15 10:40"
+            .parse::<DynPeriod>()
+            .is_err());
+        assert!("Minutes[Length:0]:2024-07-15 10:30"
+            .parse::<DynPeriod>()
+            .is_err());
+        assert!("Millis[Length:0]".parse::<DynResolution>().is_err());
+        assert!("Fortnight:2024-07-15".parse::<DynPeriod>().is_err());
+        assert!("2024-07-15".parse::<DynPeriod>().is_err());
+
+        assert_eq!(
+            "Week[StartDay:Sunday]".parse::<DynResolution>().unwrap(),
+            DynResolution::Week(chrono::Weekday::Sun)
+        );
+        assert_eq!(
+            "FiscalYear[StartMonth:July]"
+                .parse::<DynResolution>()
+                .unwrap(),
+            DynResolution::FiscalYear(chrono::Month::July)
+        );
+    }
+
+    #[test]
+    fn test_conversion() {
+        let period = DynPeriod::from(Month::from(datetime(2024, 7, 1, 0, 0)));
+        assert!(Quarter::try_from(period).is_err());
+        assert!(period.to_typed::<Week<Monday>>().is_err());
+
+        let range = TimeRange::from_bounds(period, period.succ_n(2));
+        assert_eq!(
+            range.iter().map(|p| p.to_string()).collect::<Vec<_>>(),
+            ["Month:Jul-2024", "Month:Aug-2024", "Month:Sep-2024"]
+        );
+
+        let mut resolutions = [
+            DynResolution::Year,
+            FiveMinute::dyn_resolution(),
+            DynResolution::Day,
+            Second::dyn_resolution(),
+        ];
+        resolutions.sort();
+        assert_eq!(
+            resolutions,
+            [
+                Second::dyn_resolution(),
+                FiveMinute::dyn_resolution(),
+                DynResolution::Day,
+                DynResolution::Year,
+            ]
+        );
+
+        // resolutions of the same length are only equal if they are the same variant
+        for (a, b) in [
+            (
+                Seconds::<60>::dyn_resolution(),
+                crate::Minute::dyn_resolution(),
+            ),
+            (Millis::<1000>::dyn_resolution(), Second::dyn_resolution()),
+        ] {
+            assert_ne!(a, b);
+            assert_eq!(a.cmp(&b), cmp::Ordering::Less);
+            assert_eq!(b.cmp(&a), cmp::Ordering::Greater);
+            let dt = datetime(2024, 7, 15, 10, 30);
+            let periods = [a.period(dt), b.period(dt)]
+                .into_iter()
+                .collect::<alloc::collections::BTreeSet<_>>();
+            assert_eq!(periods.len(), 2);
+        }
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        for input in [
+            "Quarter:Q5-2024",
+            "Quarter:Q0-2024",
+            "Quarter:Q12-2024",
+            "Quarter:-2024",
+            "Quarter:é-2024",
+            "Month:Jan-999999",
+            "Year:999999",
+            "FiscalYear[StartMonth:July]:FY999999",
+        ] {
+            assert!(input.parse::<DynPeriod>().is_err(), "{input}");
+            #[cfg(feature = "serde")]
+            assert!(
+                serde_json::from_str::<DynPeriod>(&serde_json::Value::from(input).to_string())
+                    .is_err(),
+                "{input}"
+            );
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let period = DynPeriod::from(Quarter::from(datetime(2024, 7, 1, 0, 0).date_naive()));
+        assert_eq!(
+            serde_json::to_string(&period).unwrap(),
+            "\"Quarter:Q3-2024\""
+        );
+        assert_eq!(
+            period,
+            serde_json::from_str(&serde_json::to_string(&period).unwrap()).unwrap()
+        );
+        assert_eq!(
+            serde_json::from_str::<DynResolution>("\"Minutes[Length:30]\"").unwrap(),
+            crate::HalfHour::dyn_resolution()
+        );
+    }
+}
@@ -0,0 +1,343 @@
+//! Alternative serde representations for periods and `TimeRange`s, for use with
+//! `#[serde(with = "...")]`:
+//!
+//! - `human`: the start and duration, eg `2024-07-15T10:30/PT5M` or `2024-01/P3M`
+//! - `interval`: an ISO-8601 interval in UTC, eg `2024-07-15T10:30Z/2024-07-15T10:35Z`
+//! - `compact`: the monotonic index of a period, or the index of the first period and the length
+//!   of a `TimeRange`
+//!
+//! ```
+//! # use resolution::{FiveMinute, Month, TimeRange};
+//! #[derive(serde::Serialize, serde::Deserialize)]
+//! struct Reading {
+//!     #[serde(with = "resolution::encoding::human")]
+//!     period: FiveMinute,
+//!     #[serde(with = "resolution::encoding::compact")]
+//!     billing: TimeRange<Month>,
+//! }
+//! ```
+//!
+//! `human` and `interval` are available for every `IsoResolution`, including `Zoned` resolutions,
+//! and `compact` for those which can also be found from their monotonic index. Each is also
+//! available for a `TimeRange` of any of them.
+
+use crate::{iso, FromMonotonic, IsoResolution, TimeRange};
+use alloc::string::String;
+use core::any;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+/// `Encode` is implemented for periods of each `IsoResolution` and for `TimeRange`s of them, so
+/// that the representations in this module are available for both
+pub trait Encode: Sized {
+    type Period: IsoResolution;
+    fn to_range(&self) -> TimeRange<Self::Period>;
+    fn from_range(range: TimeRange<Self::Period>) -> Option<Self>;
+}
+
+/// `EncodeCompact` is implemented where the periods can also be found from their monotonic index
+pub trait EncodeCompact: Encode {
+    type Compact: Serialize + de::DeserializeOwned;
+    fn to_compact(&self) -> Self::Compact;
+    fn from_compact(compact: Self::Compact) -> Option<Self>;
+}
+
+impl<P: IsoResolution> Encode for P {
+    type Period = P;
+    fn to_range(&self) -> TimeRange<P> {
+        TimeRange::from_bounds(*self, *self)
+    }
+    fn from_range(range: TimeRange<P>) -> Option<P> {
+        (range.len().get() == 1).then(|| range.start())
+    }
+}
+
+impl<P: IsoResolution + FromMonotonic> EncodeCompact for P {
+    type Compact = i64;
+    fn to_compact(&self) -> i64 {
+        self.to_monotonic()
+    }
+    fn from_compact(compact: i64) -> Option<P> {
+        Some(P::from_monotonic(compact))
+    }
+}
+
+impl<P: IsoResolution> Encode for TimeRange<P> {
+    type Period = P;
+    fn to_range(&self) -> TimeRange<P> {
+        *self
+    }
+    fn from_range(range: TimeRange<P>) -> Option<TimeRange<P>> {
+        Some(range)
+    }
+}
+
+impl<P: IsoResolution + FromMonotonic> EncodeCompact for TimeRange<P> {
+    type Compact = (i64, u64);
+    fn to_compact(&self) -> (i64, u64) {
+        (self.start().to_monotonic(), self.len().get())
+    }
+    fn from_compact((start, len): (i64, u64)) -> Option<TimeRange<P>> {
+        TimeRange::maybe_new(P::from_monotonic(start), len)
+    }
+}
+
+fn parse<T: Encode, E: de::Error>(
+    input: String,
+    parser: fn(&str) -> Option<TimeRange<T::Period>>,
+) -> Result<T, E> {
+    parser(&input).and_then(T::from_range).ok_or_else(|| {
+        E::custom(crate::Error::ParseCustom {
+            ty_name: any::type_name::<T>(),
+            input: crate::error_text(input),
+        })
+    })
+}
+
+pub mod human {
+    use super::*;
+
+    pub fn serialize<T: Encode, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&iso::StartDuration(&value.to_range()))
+    }
+
+    pub fn deserialize<'de, T: Encode, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        parse(
+            String::deserialize(deserializer)?,
+            iso::parse_start_duration,
+        )
+    }
+}
+
+pub mod interval {
+    use super::*;
+
+    pub fn serialize<T: Encode, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&iso::Interval(&value.to_range()))
+    }
+
+    pub fn deserialize<'de, T: Encode, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        parse(String::deserialize(deserializer)?, iso::parse_interval)
+    }
+}
+
+pub mod compact {
+    use super::*;
+
+    pub fn serialize<T: EncodeCompact, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        value.to_compact().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T: EncodeCompact, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        T::from_compact(T::Compact::deserialize(deserializer)?).ok_or_else(|| {
+            de::Error::invalid_value(de::Unexpected::Unsigned(0), &"a non-zero length")
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::date;
+    use crate::{
+        DateResolution, Day, FiveMinute, Hour, Millis, Minutes, Monday, Monotonic, Month, Quarter,
+        Sunday, TypedResolution, Week, Year, Zoned,
+    };
+    use chrono::Utc;
+    use serde_json::json;
+
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Encoded<P: TypedResolution> {
+        #[serde(with = "human")]
+        human: P,
+        #[serde(with = "interval")]
+        interval: P,
+        #[serde(with = "compact")]
+        compact: P,
+        #[serde(with = "human")]
+        human_range: TimeRange<P>,
+        #[serde(with = "interval")]
+        interval_range: TimeRange<P>,
+        #[serde(with = "compact")]
+        compact_range: TimeRange<P>,
+    }
+
+    impl<P: TypedResolution> Encoded<P> {
+        fn new(period: P, len: u64) -> Self {
+            let range = TimeRange::maybe_new(period, len).unwrap();
+            Encoded {
+                human: period,
+                interval: period,
+                compact: period,
+                human_range: range,
+                interval_range: range,
+                compact_range: range,
+            }
+        }
+    }
+
+    fn check<P>(encoded: Encoded<P>, expected: serde_json::Value)
+    where
+        P: TypedResolution + core::fmt::Debug,
+    {
+        let value = serde_json::to_value(&encoded).unwrap();
+        assert_eq!(value, expected);
+        assert_eq!(
+            serde_json::from_value::<Encoded<P>>(value).unwrap(),
+            encoded
+        );
+    }
+
+    #[test]
+    fn test_encodings() {
+        let datetime = date(2024, 7, 15).and_hms_opt(10, 30, 0).unwrap().and_utc();
+        let five_minute = FiveMinute::from(datetime);
+        check(
+            Encoded::new(five_minute, 12),
+            json!({
+                "human": "2024-07-15T10:30/PT5M",
+                "interval": "2024-07-15T10:30Z/2024-07-15T10:35Z",
+                "compact": five_minute.to_monotonic(),
+                "human_range": "2024-07-15T10:30/PT1H",
+                "interval_range": "2024-07-15T10:30Z/2024-07-15T11:30Z",
+                "compact_range": [five_minute.to_monotonic(), 12],
+            }),
+        );
+
+        let month = Month::from_date(date(2024, 1, 1), ());
+        check(
+            Encoded::new(month, 3),
+            json!({
+                "human": "2024-01/P1M",
+                "interval": "2024-01-01T00:00Z/2024-02-01T00:00Z",
+                "compact": month.to_monotonic(),
+                "human_range": "2024-01/P3M",
+                "interval_range": "2024-01-01T00:00Z/2024-04-01T00:00Z",
+                "compact_range": [month.to_monotonic(), 3],
+            }),
+        );
+
+        let millis = Millis::<250>::from(datetime);
+        let value = serde_json::to_value(Encoded::new(millis, 6)).unwrap();
+        assert_eq!(value["human"], "2024-07-15T10:30:00.000/PT0.250S");
+        assert_eq!(value["human_range"], "2024-07-15T10:30:00.000/PT1.500S");
+        assert_eq!(
+            value["interval"],
+            "2024-07-15T10:30:00.000Z/2024-07-15T10:30:00.250Z"
+        );
+
+        let week = Week::<Monday>::from_date(date(2024, 7, 15), ());
+        let value = serde_json::to_value(Encoded::new(week, 2)).unwrap();
+        assert_eq!(value["human"], "2024-07-15/P1W");
+        assert_eq!(value["human_range"], "2024-07-15/P2W");
+
+        let value = serde_json::to_value(Encoded::new(Year::new(2024), 1)).unwrap();
+        assert_eq!(value["human"], "2024/P1Y");
+        let value = serde_json::to_value(Encoded::new(Quarter::from_date(date(2024, 7, 1), ()), 2))
+            .unwrap();
+        assert_eq!(value["human_range"], "2024-07/P6M");
+    }
+
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct EncodedZoned<P: IsoResolution> {
+        #[serde(with = "human")]
+        human: P,
+        #[serde(with = "interval")]
+        interval: TimeRange<P>,
+    }
+
+    #[test]
+    fn test_zoned() {
+        let datetime = date(2024, 7, 15).and_hms_opt(10, 30, 0).unwrap().and_utc();
+        let hour = Zoned::<Hour, Utc>::from(datetime);
+        let encoded = EncodedZoned {
+            human: hour,
+            interval: TimeRange::maybe_new(hour, 2).unwrap(),
+        };
+        let value = serde_json::to_value(&encoded).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "human": "2024-07-15T10:00+00:00/PT1H",
+                "interval": "2024-07-15T10:00+00:00/2024-07-15T12:00+00:00",
+            })
+        );
+        assert_eq!(
+            serde_json::from_value::<EncodedZoned<_>>(value).unwrap(),
+            encoded
+        );
+
+        // zoned periods of a day or longer can't be found from their index, so only the
+        // `human` and `interval` encodings are available
+        let day = Zoned::<Day, Utc>::from_date(date(2024, 7, 15), Utc);
+        let encoded = EncodedZoned {
+            human: day,
+            interval: TimeRange::maybe_new(day, 7).unwrap(),
+        };
+        let value = serde_json::to_value(&encoded).unwrap();
+        assert_eq!(value["human"], "2024-07-15T00:00+00:00/P1D");
+        assert_eq!(
+            serde_json::from_value::<EncodedZoned<_>>(value).unwrap(),
+            encoded
+        );
+    }
+
+    #[derive(Debug, serde::Deserialize)]
+    struct Human<T: Encode>(#[serde(with = "human")] T);
+
+    #[derive(Debug, serde::Deserialize)]
+    struct Interval<T: Encode>(#[serde(with = "interval")] T);
+
+    #[derive(Debug, serde::Deserialize)]
+    struct Compact<T: EncodeCompact>(#[serde(with = "compact")] T);
+
+    #[test]
+    fn test_invalid() {
+        // the start must be the start of a period
+        assert!(serde_json::from_str::<Human<Week<Sunday>>>(r#""2024-07-15/P1W""#).is_err());
+        assert!(serde_json::from_str::<Human<Month>>(r#""2024-01-15/P1M""#).is_err());
+        assert!(serde_json::from_str::<Human<Minutes<15>>>(r#""2024-07-15T10:05/PT15M""#).is_err());
+        // the duration must be a whole number of periods
+        assert!(serde_json::from_str::<Human<TimeRange<Quarter>>>(r#""2024-07/P4M""#).is_err());
+        assert!(serde_json::from_str::<Human<TimeRange<Month>>>(r#""2024-07/P2W""#).is_err());
+        // and exactly one for a single period
+        assert!(serde_json::from_str::<Human<Month>>(r#""2024-07/P2M""#).is_err());
+        assert!(serde_json::from_str::<Interval<TimeRange<Month>>>(
+            r#""2024-02-01T00:00Z/2024-01-01T00:00Z""#
+        )
+        .is_err());
+        assert!(serde_json::from_str::<Compact<TimeRange<Month>>>("[100, 0]").is_err());
+        // lengths which overflow are rejected rather than panicking
+        assert!(serde_json::from_str::<Human<TimeRange<FiveMinute>>>(
+            r#""2024-07-15T10:30/PT5124095576030431H""#
+        )
+        .is_err());
+        assert!(serde_json::from_str::<Human<TimeRange<Month>>>(
+            r#""2024-01/P18446744073709551615M""#
+        )
+        .is_err());
+
+        let Human(range) =
+            serde_json::from_str::<Human<TimeRange<FiveMinute>>>(r#""2024-07-15T10:30/PT60M""#)
+                .unwrap();
+        assert_eq!(range.len().get(), 12);
+        let Interval(month) =
+            serde_json::from_str::<Interval<Month>>(r#""2024-07-01T00:00Z/2024-08-01T00:00Z""#)
+                .unwrap();
+        assert_eq!(month.start(), date(2024, 7, 1));
+    }
+}
@@ -0,0 +1,458 @@
+use crate::{
+    month, quarter::QuarterNumber, DateResolution, DateResolutionExt, FromMonotonic, Monotonic,
+};
+#[cfg(feature = "serde")]
+use alloc::string::{String, ToString};
+use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, Utc};
+use core::marker;
+#[cfg(feature = "serde")]
+use core::result;
+use core::{fmt, str};
+#[cfg(feature = "serde")]
+use serde::de;
+
+mod private {
+    pub trait Sealed {}
+    impl Sealed for super::January {}
+    impl Sealed for super::February {}
+    impl Sealed for super::March {}
+    impl Sealed for super::April {}
+    impl Sealed for super::May {}
+    impl Sealed for super::June {}
+    impl Sealed for super::July {}
+    impl Sealed for super::August {}
+    impl Sealed for super::September {}
+    impl Sealed for super::October {}
+    impl Sealed for super::November {}
+    impl Sealed for super::December {}
+}
+
+/// `StartMonth` is the first month of a fiscal year.
+///
+/// Fiscal years are named after the calendar year in which they end, so with a `July` start,
+/// `FY2025` runs from July 2024 to June 2025.
+pub trait StartMonth:
+    private::Sealed
+    + Send
+    + Sync
+    + 'static
+    + Copy
+    + Clone
+    + fmt::Debug
+    + PartialEq
+    + Eq
+    + PartialOrd
+    + Ord
+{
+    const NAME: &'static str;
+    fn month() -> chrono::Month;
+}
+
+macro_rules! start_month {
+    ($name:ident, $month:ident) => {
+        #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name;
+
+        impl StartMonth for $name {
+            const NAME: &'static str = stringify!($name);
+            fn month() -> chrono::Month {
+                chrono::Month::$month
+            }
+        }
+    };
+}
+
+start_month!(January, January);
+start_month!(February, February);
+start_month!(March, March);
+start_month!(April, April);
+start_month!(May, May);
+start_month!(June, June);
+start_month!(July, July);
+start_month!(August, August);
+start_month!(September, September);
+start_month!(October, October);
+start_month!(November, November);
+start_month!(December, December);
+
+// the offset from the start of the calendar year to the start of the fiscal year, in months
+fn start_offset<S: StartMonth>() -> i64 {
+    i64::from(S::month().number_from_month()) - 1
+}
+
+// the first month of the fiscal year `fy`
+fn fiscal_year_start<S: StartMonth>(fy: i64) -> month::Month {
+    let first_year = if start_offset::<S>() == 0 { fy } else { fy - 1 };
+    month::Month::from_monotonic(first_year * 12 + start_offset::<S>())
+}
+
+// the fiscal year and the number of months into that fiscal year for the given date
+fn fiscal_parts<S: StartMonth>(date: NaiveDate) -> (i64, i64) {
+    let months = i64::from(date.year()) * 12 + i64::from(date.month0()) - start_offset::<S>();
+    let first_year = months.div_euclid(12);
+    let fy = if start_offset::<S>() == 0 {
+        first_year
+    } else {
+        first_year + 1
+    };
+    (fy, months.rem_euclid(12))
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialOrd, PartialEq, Ord, Hash)]
+pub struct FiscalYear<S: StartMonth> {
+    n: i64,
+    s: marker::PhantomData<S>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialOrd, PartialEq, Ord, Hash)]
+pub struct FiscalQuarter<S: StartMonth> {
+    n: i64,
+    s: marker::PhantomData<S>,
+}
+
+impl<S: StartMonth> crate::TimeResolution for FiscalYear<S> {
+    fn succ_n(&self, n: u64) -> Self {
+        FiscalYear::from_monotonic(self.n + i64::try_from(n).unwrap())
+    }
+    fn pred_n(&self, n: u64) -> Self {
+        FiscalYear::from_monotonic(self.n - i64::try_from(n).unwrap())
+    }
+    fn start_datetime(&self) -> DateTime<Utc> {
+        self.start().and_time(NaiveTime::MIN).and_utc()
+    }
+    fn fmt_name(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "FiscalYear[StartMonth:{}]", S::NAME)
+    }
+}
+
+impl<S: StartMonth> Monotonic for FiscalYear<S> {
+    fn to_monotonic(&self) -> i64 {
+        self.n
+    }
+    fn between(&self, other: Self) -> i64 {
+        other.n - self.n
+    }
+}
+
+impl<S: StartMonth> crate::FromMonotonic for FiscalYear<S> {
+    fn from_monotonic(idx: i64) -> Self {
+        FiscalYear {
+            n: idx,
+            s: marker::PhantomData,
+        }
+    }
+}
+
+impl<S: StartMonth> crate::DateResolution for FiscalYear<S> {
+    fn start(&self) -> NaiveDate {
+        fiscal_year_start::<S>(self.n).start()
+    }
+    type Params = ();
+
+    fn params(&self) -> Self::Params {}
+
+    fn from_date(date: NaiveDate, _params: Self::Params) -> Self {
+        FiscalYear::from_monotonic(fiscal_parts::<S>(date).0)
+    }
+}
+
+impl<S: StartMonth> From<NaiveDate> for FiscalYear<S> {
+    fn from(value: NaiveDate) -> FiscalYear<S> {
+        FiscalYear::from_date(value, ())
+    }
+}
+
+impl<S: StartMonth> From<DateTime<Utc>> for FiscalYear<S> {
+    fn from(d: DateTime<Utc>) -> Self {
+        d.date_naive().into()
+    }
+}
+
+impl<S: StartMonth> FiscalYear<S> {
+    pub fn new(fy: i16) -> Self {
+        FiscalYear::from_monotonic(i64::from(fy))
+    }
+    pub fn fiscal_year_num(&self) -> i16 {
+        i16::try_from(self.n).expect("Not pre/post historic")
+    }
+    pub fn first_month(&self) -> month::Month {
+        self.start().into()
+    }
+    pub fn last_month(&self) -> month::Month {
+        self.end().into()
+    }
+    pub fn first_quarter(&self) -> FiscalQuarter<S> {
+        self.start().into()
+    }
+    pub fn last_quarter(&self) -> FiscalQuarter<S> {
+        self.end().into()
+    }
+    pub fn quarter(&self, quarter: QuarterNumber) -> FiscalQuarter<S> {
+        FiscalQuarter::from_parts(self.fiscal_year_num(), quarter)
+    }
+}
+
+impl<S: StartMonth> crate::TimeResolution for FiscalQuarter<S> {
+    fn succ_n(&self, n: u64) -> Self {
+        FiscalQuarter::from_monotonic(self.n + i64::try_from(n).unwrap())
+    }
+    fn pred_n(&self, n: u64) -> Self {
+        FiscalQuarter::from_monotonic(self.n - i64::try_from(n).unwrap())
+    }
+    fn start_datetime(&self) -> DateTime<Utc> {
+        self.start().and_time(NaiveTime::MIN).and_utc()
+    }
+    fn fmt_name(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "FiscalQuarter[StartMonth:{}]", S::NAME)
+    }
+}
+
+impl<S: StartMonth> Monotonic for FiscalQuarter<S> {
+    fn to_monotonic(&self) -> i64 {
+        self.n
+    }
+    fn between(&self, other: Self) -> i64 {
+        other.n - self.n
+    }
+}
+
+impl<S: StartMonth> crate::FromMonotonic for FiscalQuarter<S> {
+    fn from_monotonic(idx: i64) -> Self {
+        FiscalQuarter {
+            n: idx,
+            s: marker::PhantomData,
+        }
+    }
+}
+
+impl<S: StartMonth> crate::DateResolution for FiscalQuarter<S> {
+    fn start(&self) -> NaiveDate {
+        let months =
+            fiscal_year_start::<S>(self.n.div_euclid(4)).to_monotonic() + self.n.rem_euclid(4) * 3;
+        month::Month::from_monotonic(months).start()
+    }
+    type Params = ();
+
+    fn params(&self) -> Self::Params {}
+
+    fn from_date(date: NaiveDate, _params: Self::Params) -> Self {
+        let (fy, months) = fiscal_parts::<S>(date);
+        FiscalQuarter::from_monotonic(fy * 4 + months / 3)
+    }
+}
+
+impl<S: StartMonth> From<NaiveDate> for FiscalQuarter<S> {
+    fn from(value: NaiveDate) -> FiscalQuarter<S> {
+        FiscalQuarter::from_date(value, ())
+    }
+}
+
+impl<S: StartMonth> From<DateTime<Utc>> for FiscalQuarter<S> {
+    fn from(d: DateTime<Utc>) -> Self {
+        d.date_naive().into()
+    }
+}
+
+impl<S: StartMonth> FiscalQuarter<S> {
+    pub fn from_parts(fy: i16, quarter: QuarterNumber) -> Self {
+        FiscalQuarter::from_monotonic(i64::from(fy) * 4 + quarter.offset())
+    }
+    pub fn fiscal_year(&self) -> FiscalYear<S> {
+        FiscalYear::from_monotonic(self.n.div_euclid(4))
+    }
+    pub fn fiscal_year_num(&self) -> i16 {
+        self.fiscal_year().fiscal_year_num()
+    }
+    pub fn quarter_num(&self) -> u32 {
+        u32::try_from(1 + self.n.rem_euclid(4)).expect("Range of 1-4")
+    }
+    pub fn first_month(&self) -> month::Month {
+        self.start().into()
+    }
+    pub fn last_month(&self) -> month::Month {
+        self.end().into()
+    }
+}
+
+impl<S: StartMonth> fmt::Display for FiscalYear<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "FY{}", self.n)
+    }
+}
+
+impl<S: StartMonth> fmt::Display for FiscalQuarter<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "FY{}-Q{}", self.n.div_euclid(4), self.quarter_num())
+    }
+}
+
+impl<S: StartMonth> str::FromStr for FiscalYear<S> {
+    type Err = crate::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || crate::Error::ParseCustom {
+            ty_name: "FiscalYear",
+            input: crate::error_text(s),
+        };
+        let year = s.strip_prefix("FY").ok_or_else(err)?.parse::<i32>()?;
+        // the fiscal year starts in the calendar year before or of its number, and ends in the
+        // one after at the latest, so each of those must be a year `NaiveDate` can hold
+        if NaiveDate::from_ymd_opt(year.saturating_sub(1), 1, 1).is_none()
+            || NaiveDate::from_ymd_opt(year.saturating_add(1), 12, 31).is_none()
+        {
+            return Err(err());
+        }
+        Ok(FiscalYear::from_monotonic(i64::from(year)))
+    }
+}
+
+impl<S: StartMonth> str::FromStr for FiscalQuarter<S> {
+    type Err = crate::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || crate::Error::ParseCustom {
+            ty_name: "FiscalQuarter",
+            input: crate::error_text(s),
+        };
+        let (year, quarter) = s.split_once("-Q").ok_or_else(err)?;
+        let year = year.parse::<FiscalYear<S>>()?;
+        let quarter = match quarter {
+            "1" => QuarterNumber::Q1,
+            "2" => QuarterNumber::Q2,
+            "3" => QuarterNumber::Q3,
+            "4" => QuarterNumber::Q4,
+            _ => return Err(err()),
+        };
+        Ok(FiscalQuarter::from_monotonic(year.n * 4 + quarter.offset()))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, S: StartMonth> de::Deserialize<'de> for FiscalYear<S> {
+    fn deserialize<D>(deserializer: D) -> result::Result<FiscalYear<S>, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<S: StartMonth> serde::Serialize for FiscalYear<S> {
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, S: StartMonth> de::Deserialize<'de> for FiscalQuarter<S> {
+    fn deserialize<D>(deserializer: D) -> result::Result<FiscalQuarter<S>, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<S: StartMonth> serde::Serialize for FiscalQuarter<S> {
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+    where
+        Se: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::date;
+    use crate::{Day, Month, TimeRange, TimeResolution, Year};
+    use alloc::string::ToString;
+
+    #[test]
+    fn test_start_end() {
+        let fy = FiscalYear::<July>::new(2025);
+        assert_eq!(fy.start(), date(2024, 7, 1));
+        assert_eq!(fy.end(), date(2025, 6, 30));
+        assert_eq!(FiscalYear::<July>::from(date(2024, 7, 1)), fy);
+        assert_eq!(FiscalYear::<July>::from(date(2025, 6, 30)), fy);
+        assert_eq!(FiscalYear::<July>::from(date(2024, 6, 30)), fy.pred());
+
+        let q1 = fy.first_quarter();
+        assert_eq!(q1, fy.quarter(QuarterNumber::Q1));
+        assert_eq!(q1.start(), date(2024, 7, 1));
+        assert_eq!(q1.end(), date(2024, 9, 30));
+        assert_eq!(q1.succ_n(2).start(), date(2025, 1, 1));
+        assert_eq!(fy.last_quarter().end(), date(2025, 6, 30));
+        assert_eq!(fy.last_quarter().fiscal_year(), fy);
+        assert_eq!(q1.succ_n(4).fiscal_year(), fy.succ());
+
+        // a January start matches the calendar year
+        for year in 1990..2030 {
+            let fy = FiscalYear::<January>::new(year);
+            assert_eq!(fy.start(), Year::new(year).start());
+            assert_eq!(fy.end(), Year::new(year).end());
+        }
+
+        let fy = FiscalYear::<October>::new(-1);
+        assert_eq!(fy.start(), date(-2, 10, 1));
+        assert_eq!(fy.first_quarter().start(), date(-2, 10, 1));
+        assert_eq!(
+            FiscalQuarter::<October>::from(date(-1, 9, 30)),
+            fy.last_quarter()
+        );
+    }
+
+    #[test]
+    fn test_rescale() {
+        let fy = FiscalYear::<April>::new(2025);
+        let months = fy.rescale::<Month>();
+        assert_eq!(months.len().get(), 12);
+        assert_eq!(months.start(), Month::from(date(2024, 4, 1)));
+        assert_eq!(fy.rescale::<FiscalQuarter<April>>().len().get(), 4);
+        assert_eq!(fy.rescale::<Day>().len().get(), 365);
+
+        let fys = TimeRange::from_bounds(fy, fy);
+        assert_eq!(months.rescale::<FiscalYear<April>>(), fys);
+        assert_eq!(fys.rescale::<Month>(), months);
+    }
+
+    #[test]
+    fn test_parse() {
+        let fy = "FY2025".parse::<FiscalYear<July>>().unwrap();
+        assert_eq!(fy, FiscalYear::new(2025));
+        assert_eq!(fy.to_string(), "FY2025");
+
+        let q = "FY2025-Q3".parse::<FiscalQuarter<July>>().unwrap();
+        assert_eq!(q, FiscalQuarter::from_parts(2025, QuarterNumber::Q3));
+        assert_eq!(q.to_string(), "FY2025-Q3");
+        assert_eq!(q.start(), date(2025, 1, 1));
+
+        assert!("2025".parse::<FiscalYear<July>>().is_err());
+        assert!("FY2025-Q5".parse::<FiscalQuarter<July>>().is_err());
+        assert!("FY2025".parse::<FiscalQuarter<July>>().is_err());
+        assert!("FY999999".parse::<FiscalYear<July>>().is_err());
+        assert!("FY999999-Q1".parse::<FiscalQuarter<July>>().is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_roundtrip() {
+        let fy = FiscalYear::<July>::new(2025);
+        assert_eq!(serde_json::to_string(&fy).unwrap(), "\"FY2025\"");
+        assert_eq!(
+            fy,
+            serde_json::from_str(&serde_json::to_string(&fy).unwrap()).unwrap()
+        );
+        let q = fy.last_quarter();
+        assert_eq!(
+            q,
+            serde_json::from_str(&serde_json::to_string(&q).unwrap()).unwrap()
+        );
+    }
+}
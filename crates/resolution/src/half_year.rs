@@ -0,0 +1,247 @@
+use crate::{month, quarter, year, DateResolution, DateResolutionExt, Year};
+#[cfg(feature = "serde")]
+use alloc::string::{String, ToString};
+use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, Utc};
+#[cfg(feature = "serde")]
+use core::result;
+use core::{fmt, str};
+#[cfg(feature = "serde")]
+use serde::de;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct HalfYear(i64);
+
+impl crate::TimeResolution for HalfYear {
+    fn succ_n(&self, n: u64) -> Self {
+        HalfYear(self.0 + i64::try_from(n).unwrap())
+    }
+    fn pred_n(&self, n: u64) -> Self {
+        HalfYear(self.0 - i64::try_from(n).unwrap())
+    }
+    fn start_datetime(&self) -> DateTime<Utc> {
+        self.start().and_time(NaiveTime::MIN).and_utc()
+    }
+
+    fn fmt_name(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("HalfYear")
+    }
+}
+
+impl crate::Monotonic for HalfYear {
+    fn to_monotonic(&self) -> i64 {
+        self.0
+    }
+    fn between(&self, other: Self) -> i64 {
+        other.0 - self.0
+    }
+}
+
+impl crate::FromMonotonic for HalfYear {
+    fn from_monotonic(idx: i64) -> Self {
+        HalfYear(idx)
+    }
+}
+
+impl crate::DateResolution for HalfYear {
+    fn start(&self) -> chrono::NaiveDate {
+        let years = i32::try_from(self.0.div_euclid(2)).expect("Not pre/post historic");
+        let half = self.half_num();
+        chrono::NaiveDate::from_ymd_opt(years, half * 6 - 5, 1).expect("valid time")
+    }
+
+    type Params = ();
+
+    fn params(&self) -> Self::Params {}
+
+    fn from_date(d: NaiveDate, _params: Self::Params) -> Self {
+        HalfYear(i64::from(d.month0() / 6) + i64::from(d.year()) * 2)
+    }
+}
+
+impl From<NaiveDate> for HalfYear {
+    fn from(value: NaiveDate) -> HalfYear {
+        HalfYear::from_date(value, ())
+    }
+}
+
+impl From<DateTime<Utc>> for HalfYear {
+    fn from(d: DateTime<Utc>) -> Self {
+        d.date_naive().into()
+    }
+}
+
+impl HalfYear {
+    pub fn first_month(&self) -> month::Month {
+        self.start().into()
+    }
+    pub fn last_month(&self) -> month::Month {
+        self.end().into()
+    }
+    pub fn first_quarter(&self) -> quarter::Quarter {
+        self.start().into()
+    }
+    pub fn last_quarter(&self) -> quarter::Quarter {
+        self.end().into()
+    }
+    pub fn year(&self) -> year::Year {
+        super::Year::new(self.year_num())
+    }
+    pub fn year_num(&self) -> i16 {
+        Year::from(self.start()).year_num()
+    }
+    pub fn half_num(&self) -> u32 {
+        u32::try_from(1 + self.0.rem_euclid(2)).expect("Range of 1-2")
+    }
+    pub fn new(date: NaiveDate) -> Self {
+        date.into()
+    }
+    pub fn from_parts(year: i16, half: HalfYearNumber) -> Self {
+        crate::FromMonotonic::from_monotonic(i64::from(year) * 2 + half.offset())
+    }
+}
+
+pub enum HalfYearNumber {
+    H1,
+    H2,
+}
+
+impl HalfYearNumber {
+    pub(crate) fn offset(&self) -> i64 {
+        match self {
+            HalfYearNumber::H1 => 0,
+            HalfYearNumber::H2 => 1,
+        }
+    }
+}
+
+impl fmt::Display for HalfYear {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "H{}-{}", self.half_num(), self.year_num())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> de::Deserialize<'de> for HalfYear {
+    fn deserialize<D>(deserializer: D) -> result::Result<HalfYear, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        let date = s.parse::<HalfYear>().map_err(serde::de::Error::custom)?;
+        Ok(date)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for HalfYear {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let s = self.to_string();
+        serializer.serialize_str(&s)
+    }
+}
+
+impl str::FromStr for HalfYear {
+    type Err = crate::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(parsed) = chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+            return Ok(parsed.into());
+        }
+        let err = || crate::Error::ParseCustom {
+            ty_name: "HalfYear",
+            input: crate::error_text(s),
+        };
+        let (half, year) = s.split_once('-').ok_or_else(err)?;
+        let half = match half {
+            "H1" => HalfYearNumber::H1,
+            "H2" => HalfYearNumber::H2,
+            _ => return Err(err()),
+        };
+        Ok(HalfYear::from_parts(year.parse()?, half))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Month, Quarter, TimeRange, TimeResolution};
+    use alloc::string::ToString;
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_roundtrip() {
+        let dt = chrono::NaiveDate::from_ymd_opt(2021, 12, 6).unwrap();
+
+        let h = HalfYear::from(dt);
+        assert!(h.start() <= dt && h.end() >= dt);
+
+        assert_eq!(serde_json::to_string(&h).unwrap(), "\"H2-2021\"");
+        assert_eq!(
+            h,
+            serde_json::from_str(&serde_json::to_string(&h).unwrap()).unwrap()
+        )
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            "H1-2025".parse::<HalfYear>().unwrap().start(),
+            chrono::NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+        );
+        assert_eq!(
+            "H1-2025".parse::<HalfYear>().unwrap().succ().start(),
+            chrono::NaiveDate::from_ymd_opt(2025, 7, 1).unwrap(),
+        );
+        assert_eq!(
+            "H1-2025".parse::<HalfYear>().unwrap().succ().succ().start(),
+            chrono::NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(),
+        );
+        assert_eq!(
+            "2025-08-01".parse::<HalfYear>().unwrap(),
+            "H2-2025".parse::<HalfYear>().unwrap(),
+        );
+        assert_eq!(
+            "H2-2025".parse::<HalfYear>().unwrap().to_string(),
+            "H2-2025"
+        );
+        assert!("H3-2025".parse::<HalfYear>().is_err());
+        assert!("Q1-2025".parse::<HalfYear>().is_err());
+    }
+
+    #[test]
+    fn test_start() {
+        assert_eq!(
+            HalfYear(1).start(),
+            chrono::NaiveDate::from_ymd_opt(0, 7, 1).unwrap()
+        );
+        assert_eq!(
+            HalfYear(0).start(),
+            chrono::NaiveDate::from_ymd_opt(0, 1, 1).unwrap()
+        );
+        assert_eq!(
+            HalfYear(-1).start(),
+            chrono::NaiveDate::from_ymd_opt(-1, 7, 1).unwrap()
+        );
+        assert_eq!(
+            HalfYear(-2).start(),
+            chrono::NaiveDate::from_ymd_opt(-1, 1, 1).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_rescale() {
+        let h = HalfYear::from_parts(2024, HalfYearNumber::H2);
+        assert_eq!(
+            h.rescale::<Quarter>(),
+            TimeRange::from_bounds(h.first_quarter(), h.last_quarter())
+        );
+        assert_eq!(h.rescale::<Month>().len().get(), 6);
+        assert_eq!(h.rescale::<crate::Day>().len().get(), 184);
+        assert_eq!(
+            Year::new(2024).rescale::<HalfYear>(),
+            TimeRange::from_bounds(Year::new(2024).first_half(), h)
+        );
+    }
+}
@@ -0,0 +1,482 @@
+use crate::{Calendar, DateResolutionExt, Month, TimeRange, Weekend, Year};
+use alloc::{
+    collections::BTreeSet,
+    fmt, str,
+    string::{String, ToString},
+    vec::Vec,
+};
+use chrono::{Datelike, NaiveDate, Weekday};
+
+/// `DateRule` describes how to find the date of a holiday in a given year
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateRule {
+    /// The same day of the same month every year, eg `Dec 25`
+    Fixed { month: chrono::Month, day: u32 },
+    /// The nth (1-5) occurrence of a weekday in a month, eg `2nd Mon Jun`
+    NthWeekday {
+        n: u8,
+        weekday: Weekday,
+        month: chrono::Month,
+    },
+    /// The last occurrence of a weekday in a month, eg `last Mon May`
+    LastWeekday {
+        weekday: Weekday,
+        month: chrono::Month,
+    },
+    /// An offset in days from Easter Sunday, eg `Easter -2` for Good Friday
+    Easter { offset: i64 },
+    /// A one-off holiday that only occurs on a specific date, eg `2022-09-22`
+    Once(NaiveDate),
+}
+
+/// `Substitution` describes what to do when a holiday falls on the weekend
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Substitution {
+    /// The holiday is not moved
+    #[default]
+    None,
+    /// The holiday is moved to the following Monday
+    Monday,
+    /// The holiday is moved to the next day which is neither on the weekend nor already a holiday
+    Following,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HolidayRule {
+    pub name: String,
+    pub date: DateRule,
+    pub substitution: Substitution,
+}
+
+/// `HolidayRules` generates the holidays for any `Year` from a list of declarative rules.
+///
+/// The rules can be parsed from a simple line based format, where each line is either
+/// a holiday rule of the form `<name>: <date rule>[, substitute <Monday|following>]`, the
+/// weekend definition `weekend: <weekdays>`, or a `#` comment. For example:
+///
+/// ```text
+/// weekend: Sat Sun
+/// New Year's Day: Jan 1, substitute Monday
+/// Good Friday: Easter -2
+/// King's Birthday: 2nd Mon Jun
+/// Christmas Day: Dec 25, substitute following
+/// ```
+///
+/// When no weekend is specified, a Saturday and Sunday weekend is used.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HolidayRules {
+    weekend: Weekend,
+    rules: Vec<HolidayRule>,
+}
+
+impl HolidayRules {
+    pub fn new(weekend: Weekend, rules: Vec<HolidayRule>) -> Result<Self, crate::Error> {
+        if weekend.working_days() == 0 {
+            return Err(crate::Error::NoBusinessDays);
+        }
+        for rule in &rules {
+            if let DateRule::NthWeekday { n, .. } = rule.date {
+                ordinal(n)?;
+            }
+        }
+        Ok(HolidayRules { weekend, rules })
+    }
+    pub fn weekend(&self) -> Weekend {
+        self.weekend
+    }
+    pub fn rules(&self) -> &[HolidayRule] {
+        &self.rules
+    }
+    /// The observed holidays, after any substitution, for the rules which occur in `year`
+    pub fn holidays(&self, year: Year) -> BTreeSet<NaiveDate> {
+        self.named_holidays(year)
+            .into_iter()
+            .map(|(date, _)| date)
+            .collect()
+    }
+    /// The observed holidays in `year`, along with the name of the rule that produced them
+    pub fn named_holidays(&self, year: Year) -> Vec<(NaiveDate, &str)> {
+        let year = i32::from(year.year_num());
+        let mut unsubstituted = self
+            .rules
+            .iter()
+            .filter_map(|rule| Some((rule.date.in_year(year)?, rule)))
+            .collect::<Vec<_>>();
+        unsubstituted.sort_by_key(|(date, _)| *date);
+
+        let mut observed = unsubstituted
+            .iter()
+            .filter(|(date, rule)| {
+                rule.substitution == Substitution::None || !self.weekend.contains(date.weekday())
+            })
+            .map(|(date, rule)| (*date, rule.name.as_str()))
+            .collect::<Vec<_>>();
+
+        // substituted days are assigned in date order, so that earlier holidays
+        // have first choice of the following days
+        for (date, rule) in unsubstituted.iter().filter(|(date, rule)| {
+            rule.substitution != Substitution::None && self.weekend.contains(date.weekday())
+        }) {
+            let substitute = match rule.substitution {
+                Substitution::None => unreachable!(),
+                Substitution::Monday => date
+                    .iter_days()
+                    .find(|d| d.weekday() == Weekday::Mon)
+                    .expect("valid date"),
+                Substitution::Following => date
+                    .iter_days()
+                    .find(|d| {
+                        !self.weekend.contains(d.weekday()) && observed.iter().all(|o| o.0 != *d)
+                    })
+                    .expect("valid date"),
+            };
+            observed.push((substitute, rule.name.as_str()));
+        }
+
+        observed.sort_by_key(|(date, _)| *date);
+        observed
+    }
+    /// Create a `Calendar` containing all the holidays within `years`
+    pub fn calendar(&self, years: TimeRange<Year>) -> Calendar {
+        Calendar::new(
+            self.weekend,
+            years.iter().flat_map(|year| self.holidays(year)),
+        )
+        .expect("weekend is validated on creation")
+    }
+}
+
+impl DateRule {
+    /// The `n`th (1-5) occurrence of `weekday` in `month`
+    pub fn nth_weekday(
+        n: u8,
+        weekday: Weekday,
+        month: chrono::Month,
+    ) -> Result<DateRule, crate::Error> {
+        ordinal(n)?;
+        Ok(DateRule::NthWeekday { n, weekday, month })
+    }
+    /// The date this rule refers to in the given year, if any
+    pub fn in_year(&self, year: i32) -> Option<NaiveDate> {
+        match *self {
+            DateRule::Fixed { month, day } => {
+                NaiveDate::from_ymd_opt(year, month.number_from_month(), day)
+            }
+            DateRule::NthWeekday { n, weekday, month } => {
+                NaiveDate::from_weekday_of_month_opt(year, month.number_from_month(), weekday, n)
+            }
+            DateRule::LastWeekday { weekday, month } => {
+                let first = NaiveDate::from_ymd_opt(year, month.number_from_month(), 1)?;
+                let last = Month::from(first).end();
+                last.iter_days()
+                    .rev()
+                    .take(7)
+                    .find(|d| d.weekday() == weekday)
+            }
+            DateRule::Easter { offset } => {
+                easter_sunday(year)?.checked_add_signed(chrono::Duration::days(offset))
+            }
+            DateRule::Once(date) => (date.year() == year).then_some(date),
+        }
+    }
+}
+
+/// The date of Easter Sunday in the Gregorian calendar, using the anonymous Gregorian algorithm
+pub fn easter_sunday(year: i32) -> Option<NaiveDate> {
+    let a = year.rem_euclid(19);
+    let b = year.div_euclid(100);
+    let c = year.rem_euclid(100);
+    let d = b.div_euclid(4);
+    let e = b.rem_euclid(4);
+    let f = (b + 8).div_euclid(25);
+    let g = (b - f + 1).div_euclid(3);
+    let h = (19 * a + b - d - g + 15).rem_euclid(30);
+    let i = c.div_euclid(4);
+    let k = c.rem_euclid(4);
+    let l = (32 + 2 * e + 2 * i - h - k).rem_euclid(7);
+    let m = (a + 11 * h + 22 * l).div_euclid(451);
+    let month = (h + l - 7 * m + 114).div_euclid(31);
+    let day = (h + l - 7 * m + 114).rem_euclid(31) + 1;
+    NaiveDate::from_ymd_opt(year, u32::try_from(month).ok()?, u32::try_from(day).ok()?)
+}
+
+fn parse_error(input: &str) -> crate::Error {
+    crate::Error::ParseCustom {
+        ty_name: "HolidayRules",
+        input: crate::error_text(input),
+    }
+}
+
+fn month_name(month: chrono::Month) -> &'static str {
+    &month.name()[..3]
+}
+
+const ORDINALS: [&str; 5] = ["1st", "2nd", "3rd", "4th", "5th"];
+
+fn ordinal(n: u8) -> Result<&'static str, crate::Error> {
+    usize::from(n)
+        .checked_sub(1)
+        .and_then(|i| ORDINALS.get(i))
+        .copied()
+        .ok_or(crate::Error::InvalidNthWeekday(n))
+}
+
+impl str::FromStr for DateRule {
+    type Err = crate::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Some(offset) = s.strip_prefix("Easter") {
+            let offset = offset.trim();
+            let offset = if offset.is_empty() {
+                0
+            } else {
+                offset
+                    .strip_prefix('+')
+                    .unwrap_or(offset)
+                    .parse()
+                    .map_err(|e| crate::Error::ParseIntDetailed(e, crate::error_text(offset)))?
+            };
+            return Ok(DateRule::Easter { offset });
+        }
+        if let Ok(date) = NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+            return Ok(DateRule::Once(date));
+        }
+        let parts = s.split_whitespace().collect::<Vec<_>>();
+        match parts.as_slice() {
+            [month, day] => {
+                let month = month.parse().map_err(|_| parse_error(s))?;
+                let day = day
+                    .parse()
+                    .map_err(|e| crate::Error::ParseIntDetailed(e, crate::error_text(day)))?;
+                // use a leap year to check the day is valid for the month
+                NaiveDate::from_ymd_opt(2000, chrono::Month::number_from_month(&month), day)
+                    .ok_or_else(|| parse_error(s))?;
+                Ok(DateRule::Fixed { month, day })
+            }
+            [nth, weekday, month] => {
+                let weekday = weekday.parse().map_err(|_| parse_error(s))?;
+                let month = month.parse().map_err(|_| parse_error(s))?;
+                if *nth == "last" {
+                    return Ok(DateRule::LastWeekday { weekday, month });
+                }
+                let n = ORDINALS
+                    .iter()
+                    .zip(1..)
+                    .find_map(|(ordinal, n)| (ordinal == nth).then_some(n))
+                    .ok_or_else(|| parse_error(s))?;
+                Ok(DateRule::NthWeekday { n, weekday, month })
+            }
+            _ => Err(parse_error(s)),
+        }
+    }
+}
+
+impl fmt::Display for DateRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            DateRule::Fixed { month, day } => write!(f, "{} {}", month_name(month), day),
+            DateRule::NthWeekday { n, weekday, month } => match ordinal(n) {
+                Ok(ordinal) => write!(f, "{} {} {}", ordinal, weekday, month_name(month)),
+                // a rule built directly can hold any `n`, which is written as-is but rejected
+                // when parsed
+                Err(_) => write!(f, "{}th {} {}", n, weekday, month_name(month)),
+            },
+            DateRule::LastWeekday { weekday, month } => {
+                write!(f, "last {} {}", weekday, month_name(month))
+            }
+            DateRule::Easter { offset: 0 } => write!(f, "Easter"),
+            DateRule::Easter { offset } => write!(f, "Easter {offset:+}"),
+            DateRule::Once(date) => write!(f, "{date}"),
+        }
+    }
+}
+
+impl str::FromStr for HolidayRule {
+    type Err = crate::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, rule) = s.split_once(':').ok_or_else(|| parse_error(s))?;
+        let (date, substitution) = match rule.split_once(',') {
+            Some((date, substitution)) => {
+                let substitution = match substitution.trim() {
+                    "substitute Monday" => Substitution::Monday,
+                    "substitute following" => Substitution::Following,
+                    _ => return Err(parse_error(s)),
+                };
+                (date, substitution)
+            }
+            None => (rule, Substitution::None),
+        };
+        Ok(HolidayRule {
+            name: name.trim().to_string(),
+            date: date.parse()?,
+            substitution,
+        })
+    }
+}
+
+impl fmt::Display for HolidayRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.name, self.date)?;
+        match self.substitution {
+            Substitution::None => Ok(()),
+            Substitution::Monday => write!(f, ", substitute Monday"),
+            Substitution::Following => write!(f, ", substitute following"),
+        }
+    }
+}
+
+impl str::FromStr for HolidayRules {
+    type Err = crate::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut weekend = Weekend::SATURDAY_SUNDAY;
+        let mut rules = Vec::new();
+        for line in s.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(days) = line.strip_prefix("weekend:") {
+                let days = days
+                    .split_whitespace()
+                    .map(|d| d.parse::<Weekday>().map_err(|_| parse_error(line)))
+                    .collect::<Result<Vec<_>, _>>()?;
+                weekend = Weekend::new(days);
+            } else {
+                rules.push(line.parse()?);
+            }
+        }
+        HolidayRules::new(weekend, rules)
+    }
+}
+
+impl fmt::Display for HolidayRules {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let weekend = self
+            .weekend
+            .iter()
+            .map(|d| d.to_string())
+            .collect::<Vec<_>>();
+        writeln!(f, "weekend: {}", weekend.join(" "))?;
+        for rule in &self.rules {
+            writeln!(f, "{rule}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::date;
+
+    const NSW: &str = "
+# New South Wales
+weekend: Sat Sun
+New Year's Day: Jan 1, substitute Monday
+Australia Day: Jan 26, substitute Monday
+Good Friday: Easter -2
+Easter Saturday: Easter -1
+Easter Monday: Easter +1
+Anzac Day: Apr 25
+King's Birthday: 2nd Mon Jun
+Labour Day: 1st Mon Oct
+Christmas Day: Dec 25, substitute following
+Boxing Day: Dec 26, substitute following
+National Day of Mourning: 2022-09-22
+";
+
+    #[test]
+    fn test_easter() {
+        assert_eq!(easter_sunday(2019), Some(date(2019, 4, 21)));
+        assert_eq!(easter_sunday(2024), Some(date(2024, 3, 31)));
+        assert_eq!(easter_sunday(2025), Some(date(2025, 4, 20)));
+        assert_eq!(easter_sunday(2038), Some(date(2038, 4, 25)));
+    }
+
+    #[test]
+    fn test_rules() {
+        let rules = NSW.parse::<HolidayRules>().unwrap();
+
+        assert_eq!(
+            rules.holidays(Year::new(2021)),
+            BTreeSet::from([
+                date(2021, 1, 1),
+                date(2021, 1, 26),
+                date(2021, 4, 2),
+                date(2021, 4, 3),
+                date(2021, 4, 5),
+                date(2021, 4, 25),
+                date(2021, 6, 14),
+                date(2021, 10, 4),
+                date(2021, 12, 27),
+                date(2021, 12, 28),
+            ])
+        );
+
+        let holidays = rules.named_holidays(Year::new(2022));
+        assert!(holidays.contains(&(date(2022, 9, 22), "National Day of Mourning")));
+        assert!(holidays.contains(&(date(2022, 12, 26), "Boxing Day")));
+        assert!(holidays.contains(&(date(2022, 12, 27), "Christmas Day")));
+        assert!(!rules.holidays(Year::new(2023)).contains(&date(2023, 9, 22)));
+
+        // Australia Day 2025 falls on a Sunday
+        assert!(rules.holidays(Year::new(2025)).contains(&date(2025, 1, 27)));
+
+        let calendar = rules.calendar(crate::TimeRange::from_bounds(
+            Year::new(2021),
+            Year::new(2025),
+        ));
+        assert!(calendar.is_holiday(date(2024, 6, 10)));
+        assert!(!calendar.is_business_day(date(2024, 3, 29)));
+        assert!(calendar.is_business_day(date(2024, 4, 2)));
+    }
+
+    #[test]
+    fn test_last_weekday() {
+        let rule = "last Mon May".parse::<DateRule>().unwrap();
+        assert_eq!(rule.in_year(2024), Some(date(2024, 5, 27)));
+        assert_eq!(rule.in_year(2021), Some(date(2021, 5, 31)));
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let rules = NSW.parse::<HolidayRules>().unwrap();
+        assert_eq!(rules, rules.to_string().parse().unwrap());
+
+        assert!("Bad Day: Feb 30".parse::<HolidayRule>().is_err());
+        assert!("Bad Day: 6th Mon Jun".parse::<HolidayRule>().is_err());
+        assert!("Bad Day: 0th Mon Jun".parse::<HolidayRule>().is_err());
+        assert!("Bad Day: Jan 1, substitute Tuesday"
+            .parse::<HolidayRule>()
+            .is_err());
+        assert!("weekend: Mon Tue Wed Thu Fri Sat Sun"
+            .parse::<HolidayRules>()
+            .is_err());
+    }
+
+    #[test]
+    fn test_nth_weekday() {
+        let rule = DateRule::nth_weekday(5, Weekday::Fri, chrono::Month::March).unwrap();
+        assert_eq!(rule.to_string(), "5th Fri Mar");
+        assert_eq!(rule.in_year(2024), Some(date(2024, 3, 29)));
+        assert_eq!(rule.in_year(2022), None);
+
+        for n in [0, 6] {
+            assert!(matches!(
+                DateRule::nth_weekday(n, Weekday::Mon, chrono::Month::June),
+                Err(crate::Error::InvalidNthWeekday(m)) if m == n
+            ));
+            let rule = HolidayRule {
+                name: "Bad Day".to_string(),
+                date: DateRule::NthWeekday {
+                    n,
+                    weekday: Weekday::Mon,
+                    month: chrono::Month::June,
+                },
+                substitution: Substitution::None,
+            };
+            assert_eq!(rule.date.to_string(), alloc::format!("{n}th Mon Jun"));
+            assert!(rule.date.to_string().parse::<DateRule>().is_err());
+            assert!(HolidayRules::new(Weekend::SATURDAY_SUNDAY, Vec::from([rule])).is_err());
+        }
+    }
+}
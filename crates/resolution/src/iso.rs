@@ -0,0 +1,424 @@
+use crate::{DynResolution, TimeRange, TimeResolution, TypedResolution};
+use chrono::{
+    DateTime, Datelike, Days, FixedOffset, Months, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta,
+    Timelike, Utc,
+};
+use core::{fmt, str};
+
+// ISO-8601 intervals of a `TimeRange`, either as a start and end, eg
+// `2024-01-01T00:00Z/2024-04-01T00:00Z`, or as a start and duration, eg `2024-01/P3M`
+
+/// `IsoResolution` is implemented for resolutions whose `TimeRange`s can be written and parsed
+/// as ISO-8601 intervals: each `TypedResolution`, which is written in UTC, and `Zoned` resolutions
+/// in a `FixedTimeZone`, which are written in local time with the offset of each period.
+pub trait IsoResolution: TimeResolution {
+    /// The resolution of the periods, which sets their duration and the precision of their start
+    fn iso_resolution() -> DynResolution;
+    /// The offset the start of this period is written with, or `None` for UTC without an offset
+    fn iso_offset(&self) -> Option<FixedOffset>;
+    /// The period which starts at exactly `start`
+    fn from_iso_start(start: DateTime<Utc>) -> Option<Self>;
+}
+
+impl<P: TypedResolution> IsoResolution for P {
+    fn iso_resolution() -> DynResolution {
+        P::dyn_resolution()
+    }
+    fn iso_offset(&self) -> Option<FixedOffset> {
+        None
+    }
+    fn from_iso_start(start: DateTime<Utc>) -> Option<P> {
+        let period = P::containing(start);
+        (period.start_datetime() == start).then_some(period)
+    }
+}
+
+// the unit of the duration of a resolution
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Unit {
+    Millis,
+    Days,
+    Weeks,
+    Months,
+    Years,
+}
+
+// the unit of the duration of the resolution, and the number of those units in one period
+fn duration(resolution: DynResolution) -> (Unit, u64) {
+    match resolution {
+        DynResolution::Millis(n) => (Unit::Millis, u64::from(n.get())),
+        DynResolution::Seconds(n) => (Unit::Millis, u64::from(n.get()) * 1_000),
+        DynResolution::Minutes(n) => (Unit::Millis, u64::from(n.get()) * 60_000),
+        DynResolution::Day => (Unit::Days, 1),
+        DynResolution::Week(_) | DynResolution::IsoWeek => (Unit::Weeks, 1),
+        DynResolution::Month => (Unit::Months, 1),
+        DynResolution::Quarter | DynResolution::FiscalQuarter(_) => (Unit::Months, 3),
+        DynResolution::HalfYear => (Unit::Months, 6),
+        DynResolution::Year | DynResolution::FiscalYear(_) => (Unit::Years, 1),
+    }
+}
+
+fn write_duration(unit: Unit, amount: u64, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match unit {
+        Unit::Millis if amount.is_multiple_of(3_600_000) => write!(f, "PT{}H", amount / 3_600_000),
+        Unit::Millis if amount.is_multiple_of(60_000) => write!(f, "PT{}M", amount / 60_000),
+        Unit::Millis if amount.is_multiple_of(1_000) => write!(f, "PT{}S", amount / 1_000),
+        Unit::Millis => write!(f, "PT{}.{:03}S", amount / 1_000, amount % 1_000),
+        Unit::Days => write!(f, "P{amount}D"),
+        Unit::Weeks => write!(f, "P{amount}W"),
+        Unit::Months => write!(f, "P{amount}M"),
+        Unit::Years => write!(f, "P{amount}Y"),
+    }
+}
+
+// only durations with a single component are accepted, as only those can be the length of a
+// `TimeRange`
+fn parse_duration(s: &str) -> Option<(Unit, u64)> {
+    if let Some(time) = s.strip_prefix("PT") {
+        let (number, scale) = match time.as_bytes().last()? {
+            b'H' => (&time[..time.len() - 1], 3_600_000),
+            b'M' => (&time[..time.len() - 1], 60_000),
+            b'S' => (&time[..time.len() - 1], 1_000),
+            _ => return None,
+        };
+        let millis = match number.split_once('.') {
+            Some((whole, fraction)) if scale == 1_000 && (1..=3).contains(&fraction.len()) => {
+                let fraction_millis =
+                    fraction.parse::<u64>().ok()? * 10_u64.pow(3 - fraction.len() as u32);
+                whole
+                    .parse::<u64>()
+                    .ok()?
+                    .checked_mul(scale)?
+                    .checked_add(fraction_millis)?
+            }
+            Some(_) => return None,
+            None => number.parse::<u64>().ok()?.checked_mul(scale)?,
+        };
+        return Some((Unit::Millis, millis));
+    }
+    let date = s.strip_prefix('P')?;
+    let unit = match date.as_bytes().last()? {
+        b'D' => Unit::Days,
+        b'W' => Unit::Weeks,
+        b'M' => Unit::Months,
+        b'Y' => Unit::Years,
+        _ => return None,
+    };
+    Some((unit, date[..date.len() - 1].parse().ok()?))
+}
+
+// the components of a datetime needed to identify the start of a period of a resolution
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Precision {
+    Year,
+    Month,
+    Day,
+    Minute,
+    Second,
+    Millis,
+}
+
+fn precision(resolution: DynResolution) -> Precision {
+    match resolution {
+        DynResolution::Millis(_) => Precision::Millis,
+        DynResolution::Seconds(_) => Precision::Second,
+        DynResolution::Minutes(_) => Precision::Minute,
+        DynResolution::Day | DynResolution::Week(_) | DynResolution::IsoWeek => Precision::Day,
+        DynResolution::Year => Precision::Year,
+        _ => Precision::Month,
+    }
+}
+
+fn write_datetime(
+    datetime: NaiveDateTime,
+    precision: Precision,
+    f: &mut fmt::Formatter<'_>,
+) -> fmt::Result {
+    write!(f, "{:04}", datetime.year())?;
+    if precision >= Precision::Month {
+        write!(f, "-{:02}", datetime.month())?;
+    }
+    if precision >= Precision::Day {
+        write!(f, "-{:02}", datetime.day())?;
+    }
+    if precision >= Precision::Minute {
+        write!(f, "T{:02}:{:02}", datetime.hour(), datetime.minute())?;
+    }
+    if precision >= Precision::Second {
+        write!(f, ":{:02}", datetime.second())?;
+    }
+    if precision >= Precision::Millis {
+        write!(f, ".{:03}", datetime.nanosecond() / 1_000_000)?;
+    }
+    Ok(())
+}
+
+// writes the start of the `period`, in local time with its offset if it has one. As an offset
+// can only follow a time, those are written to at least the minute.
+fn write_start<P: IsoResolution>(
+    period: P,
+    precision: Precision,
+    utc_suffix: &str,
+    f: &mut fmt::Formatter<'_>,
+) -> fmt::Result {
+    let start = period.start_datetime();
+    match period.iso_offset() {
+        Some(offset) => {
+            let local = start.with_timezone(&offset).naive_local();
+            write_datetime(local, precision.max(Precision::Minute), f)?;
+            write!(f, "{offset}")
+        }
+        None => {
+            write_datetime(start.naive_utc(), precision, f)?;
+            f.write_str(utc_suffix)
+        }
+    }
+}
+
+// `+HH:MM` or `-HH:MM`
+fn parse_offset(s: &str) -> Option<FixedOffset> {
+    let (sign, offset) = match s.as_bytes().first()? {
+        b'+' => (1, &s[1..]),
+        b'-' => (-1, &s[1..]),
+        _ => return None,
+    };
+    let (hours, minutes) = offset.split_once(':')?;
+    if hours.len() != 2 || minutes.len() != 2 {
+        return None;
+    }
+    let seconds = hours.parse::<i32>().ok()? * 3600 + minutes.parse::<i32>().ok()? * 60;
+    FixedOffset::east_opt(sign * seconds)
+}
+
+// accepts any of the precisions written by `write_datetime`, followed by an optional `Z` or
+// offset. Without either the datetime is taken to be in UTC.
+fn parse_datetime(s: &str) -> Option<DateTime<Utc>> {
+    let s = s.strip_suffix('Z').unwrap_or(s);
+    let (date, time) = match s.split_once('T') {
+        Some((date, time)) => (date, Some(time)),
+        None => (s, None),
+    };
+    let mut parts = date.splitn(3, '-');
+    let year = parts.next()?.parse().ok()?;
+    let month = parts.next().map_or(Some(1), |m| m.parse().ok())?;
+    let day = parts.next().map_or(Some(1), |d| d.parse().ok())?;
+    let (time, offset) = match time {
+        Some(time) => {
+            let (time, offset) = match time.find(['+', '-']) {
+                Some(idx) => (&time[..idx], Some(parse_offset(&time[idx..])?)),
+                None => (time, None),
+            };
+            let time = NaiveTime::parse_from_str(time, "%H:%M")
+                .or_else(|_| NaiveTime::parse_from_str(time, "%H:%M:%S%.f"))
+                .ok()?;
+            (time, offset)
+        }
+        None => (NaiveTime::MIN, None),
+    };
+    let datetime = NaiveDate::from_ymd_opt(year, month, day)?.and_time(time);
+    match offset {
+        Some(offset) => Some(datetime.and_local_timezone(offset).single()?.to_utc()),
+        None => Some(datetime.and_utc()),
+    }
+}
+
+/// Displays a `TimeRange` as the start of its first period and its duration, eg `2024-01/P3M`
+/// or `2024-07-15T10:30/PT5M`
+pub(crate) struct StartDuration<'a, P: TimeResolution>(pub(crate) &'a TimeRange<P>);
+
+impl<P: IsoResolution> fmt::Display for StartDuration<'_, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let resolution = P::iso_resolution();
+        let (unit, amount) = duration(resolution);
+        write_start(self.0.start(), precision(resolution), "", f)?;
+        f.write_str("/")?;
+        write_duration(unit, amount * self.0.len().get(), f)
+    }
+}
+
+/// Displays a `TimeRange` as the start of its first period and end of its last period, eg
+/// `2024-01-01T00:00Z/2024-04-01T00:00Z`
+pub(crate) struct Interval<'a, P: TimeResolution>(pub(crate) &'a TimeRange<P>);
+
+impl<P: IsoResolution> fmt::Display for Interval<'_, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let precision = precision(P::iso_resolution()).max(Precision::Minute);
+        write_start(self.0.start(), precision, "Z", f)?;
+        f.write_str("/")?;
+        write_start(self.0.end().succ(), precision, "Z", f)
+    }
+}
+
+// the end of a duration from `start`, where it can be represented with a day to spare, so that
+// the end of a range of any resolution in any offset can be found from its start
+fn checked_end(start: DateTime<Utc>, unit: Unit, amount: u64) -> Option<DateTime<Utc>> {
+    let end =
+        match unit {
+            Unit::Millis => start
+                .checked_add_signed(TimeDelta::try_milliseconds(i64::try_from(amount).ok()?)?)?,
+            Unit::Days => start.checked_add_days(Days::new(amount))?,
+            Unit::Weeks => start.checked_add_days(Days::new(amount.checked_mul(7)?))?,
+            Unit::Months => start.checked_add_months(Months::new(u32::try_from(amount).ok()?))?,
+            Unit::Years => start
+                .checked_add_months(Months::new(u32::try_from(amount.checked_mul(12)?).ok()?))?,
+        };
+    end.checked_add_days(Days::new(1))?;
+    Some(end)
+}
+
+pub(crate) fn parse_start_duration<P: IsoResolution>(s: &str) -> Option<TimeRange<P>> {
+    let (start, duration) = s.split_once('/')?;
+    let start = P::from_iso_start(parse_datetime(start)?)?;
+    let (unit, amount) = parse_duration(duration)?;
+    let (expected, per_period) = self::duration(P::iso_resolution());
+    if unit != expected || !amount.is_multiple_of(per_period) {
+        return None;
+    }
+    checked_end(start.start_datetime(), unit, amount)?;
+    TimeRange::maybe_new(start, amount / per_period)
+}
+
+pub(crate) fn parse_interval<P: IsoResolution>(s: &str) -> Option<TimeRange<P>> {
+    let (start, end) = s.split_once('/')?;
+    let start = P::from_iso_start(parse_datetime(start)?)?;
+    let end = P::from_iso_start(parse_datetime(end)?)?;
+    TimeRange::maybe_new(start, u64::try_from(start.between(end)).ok()?)
+}
+
+/// A `TimeRange` is displayed as an ISO-8601 interval of the start of its first period and the
+/// end of its last, eg `2024-01-01T00:00Z/2024-04-01T00:00Z`, or with the alternate flag (`{:#}`)
+/// as the start and its duration, eg `2024-01/P3M`. `Zoned` ranges are written in local time with
+/// offsets, eg `2024-01-01T00:00+11:00/PT1H`.
+impl<P: IsoResolution> fmt::Display for TimeRange<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            StartDuration(self).fmt(f)
+        } else {
+            Interval(self).fmt(f)
+        }
+    }
+}
+
+/// Parses either form of ISO-8601 interval written by `Display`. Datetimes without an offset are
+/// taken to be in UTC, and each must be the start of a period.
+impl<P: IsoResolution> str::FromStr for TimeRange<P> {
+    type Err = crate::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let is_duration = s
+            .split_once('/')
+            .is_some_and(|(_, end)| end.starts_with('P'));
+        let range = if is_duration {
+            parse_start_duration(s)
+        } else {
+            parse_interval(s)
+        };
+        range.ok_or_else(|| crate::Error::ParseCustom {
+            ty_name: "TimeRange",
+            input: crate::error_text(s),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{date, Sydney};
+    use crate::{DateResolution, Day, FiveMinute, Hour, Month, Zoned};
+    use alloc::format;
+
+    fn check<P: IsoResolution + fmt::Debug>(range: TimeRange<P>, interval: &str, duration: &str) {
+        assert_eq!(format!("{range}"), interval);
+        assert_eq!(format!("{range:#}"), duration);
+        assert_eq!(interval.parse::<TimeRange<P>>().unwrap(), range);
+        assert_eq!(duration.parse::<TimeRange<P>>().unwrap(), range);
+    }
+
+    #[test]
+    fn test_utc() {
+        let month = Month::from_date(date(2024, 1, 1), ());
+        check(
+            TimeRange::new(month, 3.try_into().unwrap()),
+            "2024-01-01T00:00Z/2024-04-01T00:00Z",
+            "2024-01/P3M",
+        );
+        let five_minute =
+            FiveMinute::from(date(2024, 7, 15).and_hms_opt(23, 55, 0).unwrap().and_utc());
+        check(
+            TimeRange::new(five_minute, 2.try_into().unwrap()),
+            "2024-07-15T23:55Z/2024-07-16T00:05Z",
+            "2024-07-15T23:55/PT10M",
+        );
+        // an offset is accepted when it gives the start of a period
+        assert_eq!(
+            "2024-01-01T10:00+10:00/P3M"
+                .parse::<TimeRange<Month>>()
+                .unwrap(),
+            TimeRange::new(month, 3.try_into().unwrap())
+        );
+    }
+
+    #[test]
+    fn test_zoned() {
+        // daylight savings ends at 3am on the 7th of April 2024, when clocks go back to 2am
+        let hour = Zoned::<Hour, Sydney>::from(
+            date(2024, 4, 7)
+                .and_hms_opt(1, 0, 0)
+                .unwrap()
+                .and_local_timezone(Sydney)
+                .unwrap(),
+        );
+        check(
+            TimeRange::new(hour, 3.try_into().unwrap()),
+            "2024-04-07T01:00+11:00/2024-04-07T03:00+10:00",
+            "2024-04-07T01:00+11:00/PT3H",
+        );
+        assert_eq!(
+            "2024-04-06T14:00Z/PT3H"
+                .parse::<TimeRange<Zoned<Hour, Sydney>>>()
+                .unwrap(),
+            TimeRange::new(hour, 3.try_into().unwrap())
+        );
+
+        let day = Zoned::<Day, Sydney>::from_date(date(2024, 4, 7), Sydney);
+        check(
+            TimeRange::new(day, 2.try_into().unwrap()),
+            "2024-04-07T00:00+11:00/2024-04-09T00:00+10:00",
+            "2024-04-07T00:00+11:00/P2D",
+        );
+    }
+
+    #[test]
+    fn test_invalid() {
+        for input in [
+            "2024-01-15/P1M",
+            "2024-01/P1W",
+            "2024-01/P0M",
+            "2024-02-01T00:00Z/2024-01-01T00:00Z",
+            "2024-01-01T00:00Z/2024-01-01T00:00Z",
+            "2024-01-01T00:00+25:00/P1M",
+            "2024-01",
+            // lengths which overflow, or end after the last representable date
+            "2024-07-15T10:30/PT5124095576030431H",
+            "2024-01/P18446744073709551615M",
+            "2024-01/P4294967295M",
+        ] {
+            let crate::Error::ParseCustom {
+                ty_name,
+                input: text,
+            } = input.parse::<TimeRange<Month>>().unwrap_err()
+            else {
+                panic!("unexpected error for {input}");
+            };
+            assert_eq!(ty_name, "TimeRange");
+            // long input is truncated
+            assert!(input.starts_with(text.as_str()));
+        }
+        assert!("2024-07-15T10:30/PT5124095576030431H"
+            .parse::<TimeRange<FiveMinute>>()
+            .is_err());
+        // a `Zoned` day must start at local midnight
+        assert!("2024-04-07T00:00Z/P1D"
+            .parse::<TimeRange<Zoned<Day, Sydney>>>()
+            .is_err());
+    }
+}
@@ -0,0 +1,244 @@
+use crate::{DateResolution, FromMonotonic, Monday, Week};
+#[cfg(feature = "serde")]
+use alloc::string::{String, ToString};
+use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, Utc};
+#[cfg(feature = "serde")]
+use core::result;
+use core::{fmt, str};
+#[cfg(feature = "serde")]
+use serde::de;
+
+/// `IsoWeek` is a week starting on Monday, numbered according to ISO-8601.
+///
+/// Week 1 of an ISO year is the week containing the first Thursday of the calendar year, so the
+/// ISO year can differ from the calendar year for dates near the start or end of the year, and
+/// some ISO years have 53 weeks. It is formatted as `2024-W05`.
+#[derive(Clone, Copy, Debug, Eq, PartialOrd, PartialEq, Ord, Hash)]
+pub struct IsoWeek(i64);
+
+fn base() -> chrono::NaiveDate {
+    chrono::NaiveDate::from_ymd_opt(2021, 1, 4).expect("valid date")
+}
+
+impl IsoWeek {
+    pub fn new(date: NaiveDate) -> Self {
+        date.into()
+    }
+    /// Returns `None` if the `iso_year` does not have the given week
+    pub fn from_parts(iso_year: i32, week_number: u32) -> Option<Self> {
+        NaiveDate::from_isoywd_opt(iso_year, week_number, chrono::Weekday::Mon).map(Into::into)
+    }
+    pub fn iso_year(&self) -> i32 {
+        self.start().iso_week().year()
+    }
+    pub fn week_number(&self) -> u32 {
+        self.start().iso_week().week()
+    }
+    /// The number of weeks, either 52 or 53, in the ISO year
+    pub fn weeks_in_year(iso_year: i32) -> u32 {
+        if NaiveDate::from_isoywd_opt(iso_year, 53, chrono::Weekday::Mon).is_some() {
+            53
+        } else {
+            52
+        }
+    }
+    pub fn week(&self) -> Week<Monday> {
+        Week::from_monotonic(self.0)
+    }
+}
+
+impl Week<Monday> {
+    pub fn iso_week(&self) -> IsoWeek {
+        IsoWeek(crate::Monotonic::to_monotonic(self))
+    }
+    pub fn iso_year(&self) -> i32 {
+        self.iso_week().iso_year()
+    }
+    pub fn week_number(&self) -> u32 {
+        self.iso_week().week_number()
+    }
+}
+
+impl From<Week<Monday>> for IsoWeek {
+    fn from(value: Week<Monday>) -> IsoWeek {
+        value.iso_week()
+    }
+}
+
+impl From<IsoWeek> for Week<Monday> {
+    fn from(value: IsoWeek) -> Week<Monday> {
+        value.week()
+    }
+}
+
+impl crate::TimeResolution for IsoWeek {
+    fn succ_n(&self, n: u64) -> IsoWeek {
+        IsoWeek(self.0 + i64::try_from(n).unwrap())
+    }
+    fn pred_n(&self, n: u64) -> IsoWeek {
+        IsoWeek(self.0 - i64::try_from(n).unwrap())
+    }
+    fn start_datetime(&self) -> DateTime<Utc> {
+        self.start().and_time(NaiveTime::MIN).and_utc()
+    }
+    fn fmt_name(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("IsoWeek")
+    }
+}
+
+impl crate::Monotonic for IsoWeek {
+    fn to_monotonic(&self) -> i64 {
+        self.0
+    }
+    fn between(&self, other: Self) -> i64 {
+        other.0 - self.0
+    }
+}
+
+impl crate::FromMonotonic for IsoWeek {
+    fn from_monotonic(idx: i64) -> Self {
+        IsoWeek(idx)
+    }
+}
+
+impl crate::DateResolution for IsoWeek {
+    fn start(&self) -> chrono::NaiveDate {
+        base() + chrono::Duration::days(self.0 * 7)
+    }
+    type Params = ();
+
+    fn params(&self) -> Self::Params {}
+
+    fn from_date(date: NaiveDate, _params: Self::Params) -> Self {
+        IsoWeek((date - base()).num_days().div_euclid(7))
+    }
+}
+
+impl From<NaiveDate> for IsoWeek {
+    fn from(value: NaiveDate) -> IsoWeek {
+        IsoWeek::from_date(value, ())
+    }
+}
+
+impl From<DateTime<Utc>> for IsoWeek {
+    fn from(d: DateTime<Utc>) -> Self {
+        d.date_naive().into()
+    }
+}
+
+impl fmt::Display for IsoWeek {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-W{:02}", self.iso_year(), self.week_number())
+    }
+}
+
+impl str::FromStr for IsoWeek {
+    type Err = crate::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // accept both the extended `2024-W05` and basic `2024W05` formats
+        let (year, week) = s
+            .split_once("-W")
+            .or_else(|| s.split_once('W'))
+            .ok_or_else(|| crate::Error::ParseCustom {
+                ty_name: "IsoWeek",
+                input: crate::error_text(s),
+            })?;
+        let iso_year = year
+            .parse()
+            .map_err(|e| crate::Error::ParseIntDetailed(e, crate::error_text(year)))?;
+        let week_number = week
+            .parse()
+            .map_err(|e| crate::Error::ParseIntDetailed(e, crate::error_text(week)))?;
+        IsoWeek::from_parts(iso_year, week_number).ok_or_else(|| crate::Error::ParseCustom {
+            ty_name: "IsoWeek",
+            input: crate::error_text(s),
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> de::Deserialize<'de> for IsoWeek {
+    fn deserialize<D>(deserializer: D) -> result::Result<IsoWeek, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for IsoWeek {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::date;
+    use crate::{DateResolutionExt, TimeResolution};
+    use alloc::string::ToString;
+
+    #[test]
+    fn test_numbering() {
+        let wk = IsoWeek::from(date(2024, 1, 31));
+        assert_eq!(wk.iso_year(), 2024);
+        assert_eq!(wk.week_number(), 5);
+        assert_eq!(wk.start(), date(2024, 1, 29));
+        assert_eq!(wk.end(), date(2024, 2, 4));
+
+        // the ISO year can differ from the calendar year
+        let wk = IsoWeek::from(date(2021, 1, 3));
+        assert_eq!((wk.iso_year(), wk.week_number()), (2020, 53));
+        let wk = IsoWeek::from(date(2024, 12, 31));
+        assert_eq!((wk.iso_year(), wk.week_number()), (2025, 1));
+
+        assert_eq!(IsoWeek::weeks_in_year(2020), 53);
+        assert_eq!(IsoWeek::weeks_in_year(2021), 52);
+        assert_eq!(
+            IsoWeek::from_parts(2020, 53).unwrap().succ(),
+            IsoWeek::from_parts(2021, 1).unwrap()
+        );
+        assert!(IsoWeek::from_parts(2021, 53).is_none());
+
+        for day in date(1999, 1, 1).iter_days().take(365 * 30) {
+            let wk = IsoWeek::from(day);
+            assert!(wk.start() <= day && wk.end() >= day);
+            assert_eq!(wk.week(), Week::<Monday>::from(day));
+            assert_eq!(wk.week().iso_week(), wk);
+            assert_eq!((wk.iso_year(), wk.week_number()), {
+                let iso = day.iso_week();
+                (iso.year(), iso.week())
+            });
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let wk = "2024-W05".parse::<IsoWeek>().unwrap();
+        assert_eq!(wk.start(), date(2024, 1, 29));
+        assert_eq!(wk.to_string(), "2024-W05");
+        assert_eq!("2024W05".parse::<IsoWeek>().unwrap(), wk);
+        assert_eq!(wk.succ().to_string(), "2024-W06");
+
+        assert!("2021-W53".parse::<IsoWeek>().is_err());
+        assert!("2021-W00".parse::<IsoWeek>().is_err());
+        assert!("2021-05".parse::<IsoWeek>().is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_roundtrip() {
+        let wk = IsoWeek::from_parts(2020, 53).unwrap();
+        assert_eq!(serde_json::to_string(&wk).unwrap(), "\"2020-W53\"");
+        assert_eq!(
+            wk,
+            serde_json::from_str(&serde_json::to_string(&wk).unwrap()).unwrap()
+        )
+    }
+}
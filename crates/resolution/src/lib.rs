@@ -1,18 +1,48 @@
 #![no_std]
+#[cfg(any(feature = "alloc", test))]
 extern crate alloc;
 #[cfg(feature = "std")]
 extern crate std;
 
+#[cfg(feature = "alloc")]
+use core::any;
 use core::{
-    any, fmt,
+    fmt,
     num::{self, ParseIntError},
     str,
 };
 
 mod range;
+#[cfg(feature = "alloc")]
 use alloc::{format, string::String};
+#[cfg(not(feature = "alloc"))]
+use arrayvec::ArrayString;
 use chrono::{DateTime, NaiveDate, Utc};
-pub use range::{Cache, CacheResponse, TimeRange, TimeRangeComparison, TimeRangeIter};
+#[cfg(feature = "rkyv")]
+pub use range::ArchivedTimeRange;
+pub use range::{TimeRange, TimeRangeComparison, TimeRangeIter};
+#[cfg(feature = "alloc")]
+mod cache;
+#[cfg(feature = "alloc")]
+pub use cache::{Cache, CacheMode, CacheResponse, RangeCache, RangeCacheResponse};
+#[cfg(feature = "async")]
+mod read_through;
+#[cfg(feature = "async")]
+pub use read_through::ReadThroughCache;
+#[cfg(feature = "alloc")]
+mod range_set;
+#[cfg(feature = "alloc")]
+pub use range_set::TimeRangeSet;
+#[cfg(feature = "rkyv")]
+mod archive;
+#[cfg(feature = "serde")]
+pub mod encoding;
+mod iso;
+#[cfg(any(feature = "testing", test))]
+pub mod testing;
+#[cfg(feature = "rkyv")]
+pub use archive::{archive_tag, ArchiveTag, ArchiveZone, ArchivedPeriod};
+pub use iso::IsoResolution;
 
 mod minutes;
 pub use minutes::{DaySubdivison, Minutes};
@@ -22,22 +52,82 @@ pub type FiveMinute = Minutes<5>;
 pub type HalfHour = Minutes<30>;
 pub type Hour = Minutes<60>;
 
+mod seconds;
+pub use seconds::Seconds;
+
+pub type Second = Seconds<1>;
+
+mod millis;
+pub use millis::Millis;
+
+pub type Millisecond = Millis<1>;
+
 mod day;
 pub use day::Day;
 
 mod week;
 pub use week::{Friday, Monday, Saturday, StartDay, Sunday, Thursday, Tuesday, Wednesday, Week};
 
+mod iso_week;
+pub use iso_week::IsoWeek;
+
 mod month;
 pub use month::Month;
 mod quarter;
-pub use quarter::Quarter;
+pub use quarter::{Quarter, QuarterNumber};
+mod half_year;
+pub use half_year::{HalfYear, HalfYearNumber};
 mod year;
 pub use year::Year;
 
 mod zoned;
 pub use zoned::{FixedTimeZone, Zoned};
 
+#[cfg(feature = "alloc")]
+mod calendar;
+#[cfg(feature = "alloc")]
+pub use calendar::{BusinessCalendar, Calendar, Weekend};
+#[cfg(feature = "alloc")]
+mod business_day;
+#[cfg(feature = "alloc")]
+pub use business_day::BusinessDay;
+mod fiscal;
+pub use fiscal::{
+    April, August, December, February, FiscalQuarter, FiscalYear, January, July, June, March, May,
+    November, October, September, StartMonth,
+};
+mod dynamic;
+pub use dynamic::{DynPeriod, DynResolution, TypedResolution};
+mod parse;
+pub use parse::parse_period;
+
+#[cfg(feature = "alloc")]
+mod overlap;
+#[cfg(feature = "alloc")]
+pub use overlap::{Overlap, OverlapBasis};
+
+mod relative;
+pub use relative::RelativePeriod;
+
+#[cfg(feature = "alloc")]
+mod roll;
+#[cfg(feature = "alloc")]
+pub use roll::{DateAdjustment, DateOffset, RollConvention};
+
+#[cfg(feature = "decimal")]
+mod day_count;
+#[cfg(feature = "decimal")]
+pub use day_count::DayCount;
+
+#[cfg(feature = "alloc")]
+mod recurrence;
+#[cfg(feature = "alloc")]
+pub use recurrence::{Frequency, Occurrences, Recurrence, RecurrenceDay, RecurrenceEnd};
+#[cfg(feature = "alloc")]
+mod holidays;
+#[cfg(feature = "alloc")]
+pub use holidays::{easter_sunday, DateRule, HolidayRule, HolidayRules, Substitution};
+
 pub trait LongerThan<T>: LongerThanOrEqual<T> {}
 
 pub trait LongerThanOrEqual<T> {}
@@ -55,106 +145,255 @@ pub trait ShorterThanOrEqual<T> {}
 
 impl<Long, Short> ShorterThanOrEqual<Long> for Short where Long: LongerThan<Short> {}
 
-// TODO: use macro for this
-
-impl LongerThanOrEqual<Minute> for FiveMinute {}
-impl LongerThanOrEqual<Minute> for HalfHour {}
-impl LongerThanOrEqual<Minute> for Hour {}
-impl LongerThanOrEqual<Minute> for Day {}
-impl<D> LongerThanOrEqual<Minute> for Week<D> where D: StartDay {}
-impl LongerThanOrEqual<Minute> for Month {}
-impl LongerThanOrEqual<Minute> for Quarter {}
-impl LongerThanOrEqual<Minute> for Year {}
-
-impl LongerThan<Minute> for FiveMinute {}
-impl LongerThan<Minute> for HalfHour {}
-impl LongerThan<Minute> for Hour {}
-impl LongerThan<Minute> for Day {}
-impl<D> LongerThan<Minute> for Week<D> where D: StartDay {}
-impl LongerThan<Minute> for Month {}
-impl LongerThan<Minute> for Quarter {}
-impl LongerThan<Minute> for Year {}
-
-impl LongerThanOrEqual<FiveMinute> for HalfHour {}
-impl LongerThanOrEqual<FiveMinute> for Hour {}
-impl LongerThanOrEqual<FiveMinute> for Day {}
-impl<D> LongerThanOrEqual<FiveMinute> for Week<D> where D: StartDay {}
-impl LongerThanOrEqual<FiveMinute> for Month {}
-impl LongerThanOrEqual<FiveMinute> for Quarter {}
-impl LongerThanOrEqual<FiveMinute> for Year {}
-
-impl LongerThan<FiveMinute> for HalfHour {}
-impl LongerThan<FiveMinute> for Hour {}
-impl LongerThan<FiveMinute> for Day {}
-impl<D> LongerThan<FiveMinute> for Week<D> where D: StartDay {}
-impl LongerThan<FiveMinute> for Month {}
-impl LongerThan<FiveMinute> for Quarter {}
-impl LongerThan<FiveMinute> for Year {}
-
-impl LongerThanOrEqual<HalfHour> for Hour {}
-impl LongerThanOrEqual<HalfHour> for Day {}
-impl<D> LongerThanOrEqual<HalfHour> for Week<D> where D: StartDay {}
-impl LongerThanOrEqual<HalfHour> for Month {}
-impl LongerThanOrEqual<HalfHour> for Quarter {}
-impl LongerThanOrEqual<HalfHour> for Year {}
-
-impl LongerThan<HalfHour> for Hour {}
-impl LongerThan<HalfHour> for Day {}
-impl<D> LongerThan<HalfHour> for Week<D> where D: StartDay {}
-impl LongerThan<HalfHour> for Month {}
-impl LongerThan<HalfHour> for Quarter {}
-impl LongerThan<HalfHour> for Year {}
-
-impl LongerThanOrEqual<Hour> for Day {}
-impl<D> LongerThanOrEqual<Hour> for Week<D> where D: StartDay {}
-impl LongerThanOrEqual<Hour> for Month {}
-impl LongerThanOrEqual<Hour> for Quarter {}
-impl LongerThanOrEqual<Hour> for Year {}
-
-impl LongerThan<Hour> for Day {}
-impl<D> LongerThan<Hour> for Week<D> where D: StartDay {}
-impl LongerThan<Hour> for Month {}
-impl LongerThan<Hour> for Quarter {}
-impl LongerThan<Hour> for Year {}
-
-impl<D> LongerThanOrEqual<Day> for Week<D> where D: StartDay {}
-impl LongerThanOrEqual<Day> for Month {}
-impl LongerThanOrEqual<Day> for Quarter {}
-impl LongerThanOrEqual<Day> for Year {}
-
-impl<D> LongerThan<Day> for Week<D> where D: StartDay {}
-impl LongerThan<Day> for Month {}
-impl LongerThan<Day> for Quarter {}
-impl LongerThan<Day> for Year {}
-
-impl<D0> LongerThanOrEqual<Week<D0>> for Quarter where D0: StartDay {}
-impl<D0> LongerThanOrEqual<Week<D0>> for Month where D0: StartDay {}
-impl<D0> LongerThanOrEqual<Week<D0>> for Year where D0: StartDay {}
-
-impl<D0> LongerThan<Week<D0>> for Month where D0: StartDay {}
-impl<D0> LongerThan<Week<D0>> for Quarter where D0: StartDay {}
-impl<D0> LongerThan<Week<D0>> for Year where D0: StartDay {}
-
-impl LongerThanOrEqual<Month> for Quarter {}
-impl LongerThanOrEqual<Month> for Year {}
-
-impl LongerThan<Month> for Quarter {}
-impl LongerThan<Month> for Year {}
-
-impl LongerThanOrEqual<Quarter> for Year {}
-
-impl LongerThan<Quarter> for Year {}
+/// `Rescalable<T>` is implemented in both directions between resolutions related by
+/// `LongerThan`, and from each resolution to itself, so that `TimeRange::rescale` is only
+/// available between resolutions whose periods line up. Where one resolution divides evenly
+/// into the other, rescaling covers exactly the same time, otherwise, such as between `Week`s
+/// and `Month`s, the rescaled range covers every period which overlaps the original range.
+pub trait Rescalable<T> {}
+
+// rescaling to the same resolution is implemented for each resolution rather than for all
+// types, so that it doesn't overlap with rescaling between `Zoned` resolutions
+macro_rules! rescalable_to_self {
+    ($([$($g:tt)*] $res:ty),+ $(,)?) => {
+        $(impl<$($g)*> Rescalable<$res> for $res {})+
+    };
+}
+
+rescalable_to_self!(
+    [const N: u32] Minutes<N>,
+    [const N: u32] Seconds<N>,
+    [const N: u32] Millis<N>,
+    [] Day,
+    [D: StartDay] Week<D>,
+    [] IsoWeek,
+    [] Month,
+    [] Quarter,
+    [] HalfYear,
+    [] Year,
+    [S: StartMonth] FiscalQuarter<S>,
+    [S: StartMonth] FiscalYear<S>,
+    [] DynPeriod,
+);
+#[cfg(feature = "alloc")]
+rescalable_to_self!([C: BusinessCalendar] BusinessDay<C>);
+
+impl<R1, R2, Z> Rescalable<Zoned<R2, Z>> for Zoned<R1, Z>
+where
+    R1: TimeResolution + Rescalable<R2>,
+    R2: TimeResolution,
+    Z: chrono::TimeZone + Copy + fmt::Debug,
+{
+}
+
+// `longer_than!([generics] Long => [Short, ...])` relates `Long` to each `Short`, and
+// `longer_than!([generics] [Long, ...] => [Short, ...])` relates each `Long` to each `Short`.
+macro_rules! longer_than {
+    (@impl [$($g:tt)*] $long:ty, $short:ty) => {
+        impl<$($g)*> LongerThanOrEqual<$short> for $long {}
+        impl<$($g)*> LongerThan<$short> for $long {}
+        impl<$($g)*> Rescalable<$short> for $long {}
+        impl<$($g)*> Rescalable<$long> for $short {}
+    };
+    ($gens:tt [$($long:ty),+ $(,)?] => $shorts:tt) => {
+        $(longer_than!($gens $long => $shorts);)+
+    };
+    ($gens:tt $long:ty => [$($short:ty),+ $(,)?]) => {
+        $(longer_than!(@impl $gens $long, $short);)+
+    };
+}
+
+// the resolutions of a week or longer
+macro_rules! longer_than_days {
+    ([$($g:tt)*] $shorts:tt) => {
+        longer_than!([$($g)*] [IsoWeek, Month, Quarter, HalfYear, Year] => $shorts);
+        longer_than!([D: StartDay, $($g)*] Week<D> => $shorts);
+        longer_than!([S: StartMonth, $($g)*] [FiscalQuarter<S>, FiscalYear<S>] => $shorts);
+    };
+}
+
+macro_rules! longer_than_sub_day {
+    ($($short:ty),+ $(,)?) => {
+        longer_than!([] Day => [$($short),+]);
+        #[cfg(feature = "alloc")]
+        longer_than!([C: BusinessCalendar] BusinessDay<C> => [$($short),+]);
+        longer_than_days!([] [$($short),+]);
+    };
+}
+
+// `$res<N>` is longer than `$res<M>` only where `M` divides evenly into `N`, which is checked
+// when the crate is compiled
+macro_rules! divisible_longer_than {
+    ($res:ident: $($long:literal => [$($short:literal),+]),+ $(,)?) => {
+        $($(
+            #[allow(clippy::modulo_one)]
+            const _: () = assert!($long % $short == 0);
+            longer_than!([] $res<$long> => [$res<$short>]);
+        )+)+
+    };
+}
+
+// `Minutes` are generated for lengths which divide evenly into an hour, and for whole numbers
+// of hours which divide evenly into a day
+divisible_longer_than!(
+    Minutes:
+    2 => [1],
+    3 => [1],
+    4 => [1, 2],
+    5 => [1],
+    6 => [1, 2, 3],
+    10 => [1, 2, 5],
+    12 => [1, 2, 3, 4, 6],
+    15 => [1, 3, 5],
+    20 => [1, 2, 4, 5, 10],
+    30 => [1, 2, 3, 5, 6, 10, 15],
+    60 => [1, 2, 3, 4, 5, 6, 10, 12, 15, 20, 30],
+    120 => [1, 2, 3, 4, 5, 6, 10, 12, 15, 20, 30, 60],
+    180 => [1, 2, 3, 4, 5, 6, 10, 12, 15, 20, 30, 60],
+    240 => [1, 2, 3, 4, 5, 6, 10, 12, 15, 20, 30, 60, 120],
+    360 => [1, 2, 3, 4, 5, 6, 10, 12, 15, 20, 30, 60, 120, 180],
+    480 => [1, 2, 3, 4, 5, 6, 10, 12, 15, 20, 30, 60, 120, 240],
+    720 => [1, 2, 3, 4, 5, 6, 10, 12, 15, 20, 30, 60, 120, 180, 240, 360],
+    1440 => [1, 2, 3, 4, 5, 6, 10, 12, 15, 20, 30, 60, 120, 180, 240, 360, 480, 720],
+);
+
+// `Seconds` and `Millis` are only generated for lengths which divide evenly into a minute
+// and a second respectively
+divisible_longer_than!(
+    Seconds:
+    2 => [1],
+    3 => [1],
+    4 => [1, 2],
+    5 => [1],
+    6 => [1, 2, 3],
+    10 => [1, 2, 5],
+    12 => [1, 2, 3, 4, 6],
+    15 => [1, 3, 5],
+    20 => [1, 2, 4, 5, 10],
+    30 => [1, 2, 3, 5, 6, 10, 15],
+);
+
+divisible_longer_than!(
+    Millis:
+    2 => [1],
+    4 => [1, 2],
+    5 => [1],
+    10 => [1, 2, 5],
+    20 => [1, 2, 4, 5, 10],
+    25 => [1, 5],
+    50 => [1, 2, 5, 10, 25],
+    100 => [1, 2, 4, 5, 10, 20, 25, 50],
+    200 => [1, 2, 4, 5, 10, 20, 25, 50, 100],
+    250 => [1, 2, 5, 10, 25, 50],
+    500 => [1, 2, 4, 5, 10, 20, 25, 50, 100, 250],
+);
+
+longer_than!(
+    [] [
+        Minutes<1>, Minutes<2>, Minutes<3>, Minutes<4>, Minutes<5>, Minutes<6>, Minutes<10>,
+        Minutes<12>, Minutes<15>, Minutes<20>, Minutes<30>, Minutes<60>, Minutes<120>,
+        Minutes<180>, Minutes<240>, Minutes<360>, Minutes<480>, Minutes<720>, Minutes<1440>,
+    ] => [
+        Seconds<1>, Seconds<2>, Seconds<3>, Seconds<4>, Seconds<5>, Seconds<6>, Seconds<10>,
+        Seconds<12>, Seconds<15>, Seconds<20>, Seconds<30>,
+        Millis<1>, Millis<2>, Millis<4>, Millis<5>, Millis<10>, Millis<20>, Millis<25>,
+        Millis<50>, Millis<100>, Millis<200>, Millis<250>, Millis<500>,
+    ]
+);
+
+longer_than!(
+    [] [
+        Seconds<1>, Seconds<2>, Seconds<3>, Seconds<4>, Seconds<5>, Seconds<6>, Seconds<10>,
+        Seconds<12>, Seconds<15>, Seconds<20>, Seconds<30>,
+    ] => [
+        Millis<1>, Millis<2>, Millis<4>, Millis<5>, Millis<10>, Millis<20>, Millis<25>,
+        Millis<50>, Millis<100>, Millis<200>, Millis<250>, Millis<500>,
+    ]
+);
+
+longer_than_sub_day!(
+    Minutes<1>,
+    Minutes<2>,
+    Minutes<3>,
+    Minutes<4>,
+    Minutes<5>,
+    Minutes<6>,
+    Minutes<10>,
+    Minutes<12>,
+    Minutes<15>,
+    Minutes<20>,
+    Minutes<30>,
+    Minutes<60>,
+    Minutes<120>,
+    Minutes<180>,
+    Minutes<240>,
+    Minutes<360>,
+    Minutes<480>,
+    Minutes<720>,
+    Seconds<1>,
+    Seconds<2>,
+    Seconds<3>,
+    Seconds<4>,
+    Seconds<5>,
+    Seconds<6>,
+    Seconds<10>,
+    Seconds<12>,
+    Seconds<15>,
+    Seconds<20>,
+    Seconds<30>,
+    Millis<1>,
+    Millis<2>,
+    Millis<4>,
+    Millis<5>,
+    Millis<10>,
+    Millis<20>,
+    Millis<25>,
+    Millis<50>,
+    Millis<100>,
+    Millis<200>,
+    Millis<250>,
+    Millis<500>,
+);
+
+// a `Minutes<1440>` is the same length as a `Day`
+longer_than_days!([] [Minutes<1440>]);
+
+longer_than_days!([][Day]);
+#[cfg(feature = "alloc")]
+longer_than_days!([C: BusinessCalendar] [BusinessDay<C>]);
+longer_than!([D: StartDay] [Month, Quarter, HalfYear, Year] => [Week<D>]);
+longer_than!([S: StartMonth, D: StartDay] [FiscalQuarter<S>, FiscalYear<S>] => [Week<D>]);
+longer_than!([] [Month, Quarter, HalfYear, Year] => [IsoWeek]);
+longer_than!([S: StartMonth] [FiscalQuarter<S>, FiscalYear<S>] => [IsoWeek]);
+longer_than!([] [Quarter, HalfYear, Year] => [Month]);
+longer_than!([S: StartMonth] [FiscalQuarter<S>, FiscalYear<S>] => [Month]);
+longer_than!([] [HalfYear, Year] => [Quarter]);
+longer_than!([] Year => [HalfYear]);
+longer_than!([S: StartMonth] FiscalYear<S> => [FiscalQuarter<S>]);
+
+#[cfg(feature = "alloc")]
+impl<C: BusinessCalendar> LongerThanOrEqual<BusinessDay<C>> for Day {}
+
+impl LongerThanOrEqual<Week<Monday>> for IsoWeek {}
+impl LongerThanOrEqual<IsoWeek> for Week<Monday> {}
+impl Rescalable<Week<Monday>> for IsoWeek {}
+impl Rescalable<IsoWeek> for Week<Monday> {}
 
 /// This function is useful for formatting types implementing `Monotonic` when they are stored
 /// in their `i64` form instead of their `TimeResolution` form. Provided you have the `TypeId` handy
 /// you can find out what they were intended to be. This function handeles all the cases implemented
 /// in this library and users can handle others via the function in the `handle_unknown` parameter.
+#[cfg(feature = "alloc")]
 pub fn format_erased_resolution(
     handle_unknown: fn(any::TypeId, i64) -> String,
     tid: any::TypeId,
     val: i64,
 ) -> String {
-    if tid == any::TypeId::of::<Minute>() {
+    if tid == any::TypeId::of::<Millisecond>() {
+        format!("Millisecond:{}", Millisecond::from_monotonic(val))
+    } else if tid == any::TypeId::of::<Second>() {
+        format!("Second:{}", Second::from_monotonic(val))
+    } else if tid == any::TypeId::of::<Minute>() {
         format!("Minute:{}", Minute::from_monotonic(val))
     } else if tid == any::TypeId::of::<FiveMinute>() {
         format!("FiveMinute:{}", FiveMinute::from_monotonic(val))
@@ -178,10 +417,14 @@ pub fn format_erased_resolution(
         format!("Week:{}", Week::<week::Saturday>::from_monotonic(val))
     } else if tid == any::TypeId::of::<Week<week::Sunday>>() {
         format!("Week:{}", Week::<week::Sunday>::from_monotonic(val))
+    } else if tid == any::TypeId::of::<IsoWeek>() {
+        format!("IsoWeek:{}", IsoWeek::from_monotonic(val))
     } else if tid == any::TypeId::of::<Month>() {
         format!("Month:{}", Month::from_monotonic(val))
     } else if tid == any::TypeId::of::<Quarter>() {
         format!("Quarter:{}", Quarter::from_monotonic(val))
+    } else if tid == any::TypeId::of::<HalfYear>() {
+        format!("HalfYear:{}", HalfYear::from_monotonic(val))
     } else if tid == any::TypeId::of::<Year>() {
         format!("Year:{}", Year::from_monotonic(val))
     } else {
@@ -189,18 +432,68 @@ pub fn format_erased_resolution(
     }
 }
 
+/// The capacity of an `ErrorText` when the `alloc` feature isn't enabled
+pub const ERROR_TEXT_CAPACITY: usize = 32;
+
+/// `ErrorText` is the text stored in an `Error`, such as the input which couldn't be parsed. With
+/// the `alloc` feature it holds the full text.
+#[cfg(feature = "alloc")]
+pub type ErrorText = String;
+
+/// `ErrorText` is the text stored in an `Error`, such as the input which couldn't be parsed.
+/// Without the `alloc` feature it has a fixed capacity of `ERROR_TEXT_CAPACITY` bytes, so that
+/// creating an `Error` never allocates, and longer text is truncated.
+#[cfg(not(feature = "alloc"))]
+pub type ErrorText = ArrayString<ERROR_TEXT_CAPACITY>;
+
+#[cfg(feature = "alloc")]
+pub(crate) fn error_text(text: impl fmt::Display) -> ErrorText {
+    format!("{text}")
+}
+
+// writes into an `ErrorText`, dropping everything from the first character which doesn't fit
+#[cfg(not(feature = "alloc"))]
+struct Truncate {
+    text: ErrorText,
+    full: bool,
+}
+
+#[cfg(not(feature = "alloc"))]
+impl fmt::Write for Truncate {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            if self.full || self.text.try_push(c).is_err() {
+                self.full = true;
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+pub(crate) fn error_text(text: impl fmt::Display) -> ErrorText {
+    let mut truncate = Truncate {
+        text: ErrorText::new(),
+        full: false,
+    };
+    // `Truncate` never fails, so this only errors if the `Display` impl does
+    let _ = fmt::Write::write_fmt(&mut truncate, format_args!("{text}"));
+    truncate.text
+}
+
 #[derive(Debug)]
 pub enum Error {
     GotNonMatchingNewData {
-        point: String,
-        old: String,
-        new: String,
+        point: ErrorText,
+        old: ErrorText,
+        new: ErrorText,
     },
     ParseInt(num::ParseIntError),
     ParseDate(chrono::ParseError),
     ParseCustom {
         ty_name: &'static str,
-        input: String,
+        input: ErrorText,
     },
     EmptyRange,
     UnexpectedStartDate {
@@ -213,12 +506,30 @@ pub enum Error {
         actual: usize,
         format: &'static str,
     },
-    ParseIntDetailed(ParseIntError, String),
+    ParseIntDetailed(ParseIntError, ErrorText),
     ParseDateInternal {
-        message: String,
-        input: String,
+        message: ErrorText,
+        input: ErrorText,
         format: &'static str,
     },
+    NoBusinessDays,
+    UnexpectedResolution {
+        required: DynResolution,
+        actual: DynResolution,
+    },
+    ParseUnrecognisedPeriod {
+        input: ErrorText,
+        attempted: &'static [&'static str],
+    },
+    InvalidRecurrence {
+        rule: ErrorText,
+        reason: &'static str,
+    },
+    InvalidNthWeekday(u8),
+    UnmeasurableOverlap {
+        resolution: ErrorText,
+        basis: &'static str,
+    },
 }
 
 impl From<num::ParseIntError> for Error {
@@ -278,6 +589,38 @@ impl fmt::Display for Error {
                     "Error parsing {input} as date due to {message} using format {format}"
                 )
             }
+            NoBusinessDays => write!(f, "Calendar must have at least one business day per week"),
+            UnexpectedResolution { required, actual } => {
+                write!(
+                    f,
+                    "Unexpected resolution, got {actual} but needed {required}"
+                )
+            }
+            ParseUnrecognisedPeriod { input, attempted } => {
+                write!(
+                    f,
+                    "Error parsing period from input: {input}, attempted formats: "
+                )?;
+                for (idx, format) in attempted.iter().enumerate() {
+                    if idx > 0 {
+                        f.write_str(", ")?;
+                    }
+                    f.write_str(format)?;
+                }
+                Ok(())
+            }
+            InvalidRecurrence { rule, reason } => {
+                write!(f, "Invalid recurrence rule {rule}: {reason}")
+            }
+            UnmeasurableOverlap { resolution, basis } => {
+                write!(f, "Periods of {resolution} are shorter than one of the {basis} they are measured in")
+            }
+            InvalidNthWeekday(n) => {
+                write!(
+                    f,
+                    "Weekday occurrence in a month must be from 1 to 5, not {n}"
+                )
+            }
         }
     }
 }
@@ -309,7 +652,18 @@ pub trait TimeResolution: Copy + Eq + Ord + Monotonic {
 
     fn start_datetime(&self) -> DateTime<Utc>;
 
-    fn name(&self) -> String;
+    /// Write the name of the resolution, eg `Minutes[Length:5]`
+    fn fmt_name(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+
+    /// The name of the resolution, which can be displayed without allocating
+    fn display_name(&self) -> ResolutionName<'_, Self> {
+        ResolutionName(self)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn name(&self) -> String {
+        format!("{}", self.display_name())
+    }
 
     fn convert<Out>(&self) -> Out
     where
@@ -339,6 +693,15 @@ pub trait TimeResolution: Copy + Eq + Ord + Monotonic {
     }
 }
 
+/// `ResolutionName` displays the name of a resolution, as returned by `TimeResolution::display_name`
+pub struct ResolutionName<'a, P: ?Sized>(&'a P);
+
+impl<P: TimeResolution> fmt::Display for ResolutionName<'_, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt_name(f)
+    }
+}
+
 /// `Monotonic` is used to enable multiple different resolutions to be stored together
 ///
 /// It is named monotonic as it is intended to provide a monotonic (order preserving) function
@@ -423,11 +786,24 @@ pub trait DateResolutionExt: DateResolution {
             Out::from_date(self.end(), self.params()),
         )
     }
+
+    /// The business days within this period, or `None` if there are none
+    #[cfg(feature = "alloc")]
+    fn to_business_days<C>(&self, calendar: C) -> Option<range::TimeRange<BusinessDay<C>>>
+    where
+        C: BusinessCalendar,
+    {
+        let first = BusinessDay::following(self.start(), calendar);
+        let last = BusinessDay::preceding(self.end(), calendar);
+        (first <= last).then(|| range::TimeRange::from_bounds(first, last))
+    }
 }
 
 impl<T> DateResolutionExt for T where T: DateResolution {}
 
-trait DateResolutionBuilder {
+pub trait DateResolutionBuilder {
+    fn h1(self) -> HalfYear;
+    fn h2(self) -> HalfYear;
     fn q1(self) -> Quarter;
     fn q2(self) -> Quarter;
     fn q3(self) -> Quarter;
@@ -446,69 +822,69 @@ trait DateResolutionBuilder {
     fn dec(self) -> Month;
 }
 impl DateResolutionBuilder for i16 {
+    fn h1(self) -> HalfYear {
+        HalfYear::from_parts(self, half_year::HalfYearNumber::H1)
+    }
+    fn h2(self) -> HalfYear {
+        HalfYear::from_parts(self, half_year::HalfYearNumber::H2)
+    }
     fn q1(self) -> Quarter {
-        Quarter::from_parts(
-            <Self as Into<i16>>::into(self).into(),
-            quarter::QuarterNumber::Q1,
-        )
+        Quarter::from_parts(self, quarter::QuarterNumber::Q1)
     }
     fn q2(self) -> Quarter {
-        Quarter::from_parts(
-            <Self as Into<i16>>::into(self).into(),
-            quarter::QuarterNumber::Q2,
-        )
+        Quarter::from_parts(self, quarter::QuarterNumber::Q2)
     }
     fn q3(self) -> Quarter {
-        Quarter::from_parts(
-            <Self as Into<i16>>::into(self).into(),
-            quarter::QuarterNumber::Q3,
-        )
+        Quarter::from_parts(self, quarter::QuarterNumber::Q3)
     }
     fn q4(self) -> Quarter {
-        Quarter::from_parts(
-            <Self as Into<i16>>::into(self).into(),
-            quarter::QuarterNumber::Q4,
-        )
+        Quarter::from_parts(self, quarter::QuarterNumber::Q4)
     }
     fn jan(self) -> Month {
-        Month::from_year_month(self.into(), chrono::Month::January)
+        Month::from_year_month(self, chrono::Month::January)
     }
     fn feb(self) -> Month {
-        Month::from_year_month(self.into(), chrono::Month::February)
+        Month::from_year_month(self, chrono::Month::February)
     }
     fn mar(self) -> Month {
-        Month::from_year_month(self.into(), chrono::Month::March)
+        Month::from_year_month(self, chrono::Month::March)
     }
     fn apr(self) -> Month {
-        Month::from_year_month(self.into(), chrono::Month::April)
+        Month::from_year_month(self, chrono::Month::April)
     }
     fn may(self) -> Month {
-        Month::from_year_month(self.into(), chrono::Month::May)
+        Month::from_year_month(self, chrono::Month::May)
     }
     fn jun(self) -> Month {
-        Month::from_year_month(self.into(), chrono::Month::June)
+        Month::from_year_month(self, chrono::Month::June)
     }
     fn jul(self) -> Month {
-        Month::from_year_month(self.into(), chrono::Month::July)
+        Month::from_year_month(self, chrono::Month::July)
     }
     fn aug(self) -> Month {
-        Month::from_year_month(self.into(), chrono::Month::August)
+        Month::from_year_month(self, chrono::Month::August)
     }
     fn sep(self) -> Month {
-        Month::from_year_month(self.into(), chrono::Month::September)
+        Month::from_year_month(self, chrono::Month::September)
     }
     fn oct(self) -> Month {
-        Month::from_year_month(self.into(), chrono::Month::October)
+        Month::from_year_month(self, chrono::Month::October)
     }
     fn nov(self) -> Month {
-        Month::from_year_month(self.into(), chrono::Month::November)
+        Month::from_year_month(self, chrono::Month::November)
     }
     fn dec(self) -> Month {
-        Month::from_year_month(self.into(), chrono::Month::December)
+        Month::from_year_month(self, chrono::Month::December)
     }
 }
 
 impl DateResolutionBuilder for Year {
+    fn h1(self) -> HalfYear {
+        HalfYear::from_parts(self.year_num(), half_year::HalfYearNumber::H1)
+    }
+    fn h2(self) -> HalfYear {
+        HalfYear::from_parts(self.year_num(), half_year::HalfYearNumber::H2)
+    }
     fn q1(self) -> Quarter {
         Quarter::from_parts(self.year_num(), quarter::QuarterNumber::Q1)
     }
@@ -559,16 +935,105 @@ impl DateResolutionBuilder for Year {
     }
 }
 
+#[cfg(test)]
+fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+#[cfg(test)]
+fn days(start: i64, end: i64) -> TimeRange<Day> {
+    TimeRange::from_bounds(Day::from_monotonic(start), Day::from_monotonic(end))
+}
+
+// a `FixedTimeZone` with daylight savings, for testing `Zoned` resolutions
+#[cfg(test)]
+mod sydney {
+    use crate::FixedTimeZone;
+    use chrono::{FixedOffset, MappedLocalTime, NaiveDate, NaiveDateTime, Offset, TimeZone};
+
+    #[derive(Debug, Clone, Copy)]
+    pub(crate) struct Sydney;
+
+    impl TimeZone for Sydney {
+        type Offset = FixedOffset;
+        fn from_offset(_: &FixedOffset) -> Self {
+            Sydney
+        }
+        fn offset_from_local_date(&self, local: &NaiveDate) -> MappedLocalTime<FixedOffset> {
+            chrono_tz::Australia::Sydney
+                .offset_from_local_date(local)
+                .map(|o| o.fix())
+        }
+        fn offset_from_local_datetime(
+            &self,
+            local: &NaiveDateTime,
+        ) -> MappedLocalTime<FixedOffset> {
+            chrono_tz::Australia::Sydney
+                .offset_from_local_datetime(local)
+                .map(|o| o.fix())
+        }
+        fn offset_from_utc_date(&self, utc: &NaiveDate) -> FixedOffset {
+            chrono_tz::Australia::Sydney.offset_from_utc_date(utc).fix()
+        }
+        fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> FixedOffset {
+            chrono_tz::Australia::Sydney
+                .offset_from_utc_datetime(utc)
+                .fix()
+        }
+    }
+
+    impl FixedTimeZone for Sydney {
+        fn new() -> Self {
+            Sydney
+        }
+    }
+}
+#[cfg(test)]
+use sydney::Sydney;
+
 #[cfg(test)]
 mod tests {
     use quarter::QuarterNumber;
 
     use super::*;
 
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_error_text() {
+        let input = "Q1-2024-and-some-more-text-which-is-too-long";
+        let Err(Error::ParseCustom { input: text, .. }) = input.parse::<Quarter>() else {
+            panic!("expected a parse error");
+        };
+        assert_eq!(text, input);
+    }
+
+    #[test]
+    #[cfg(not(feature = "alloc"))]
+    fn test_error_text_truncated() {
+        assert_eq!(error_text("2024-Q5").as_str(), "2024-Q5");
+        assert_eq!(
+            error_text(format_args!("{}", "0123456789".repeat(4))).as_str(),
+            "01234567890123456789012345678901"
+        );
+        // truncation keeps whole characters
+        let text = error_text("é".repeat(20));
+        assert_eq!(text.len(), ERROR_TEXT_CAPACITY);
+        assert!(text.chars().all(|c| c == 'é'));
+
+        let input = "Q1-2024-and-some-more-text-which-is-too-long";
+        let Err(Error::ParseCustom { input: text, .. }) = input.parse::<Quarter>() else {
+            panic!("expected a parse error");
+        };
+        assert_eq!(text.as_str(), &input[..ERROR_TEXT_CAPACITY]);
+    }
+
     #[test]
     fn test_builder() {
         assert_eq!(2024.q1(), Quarter::from_parts(2024, QuarterNumber::Q1));
         assert_eq!(2024.q1(), Year::new(2024).first_quarter());
         assert_eq!(Year::new(2024).q1(), Year::new(2024).first_quarter());
+        assert_eq!(2024.h2(), HalfYear::from_parts(2024, HalfYearNumber::H2));
+        assert_eq!(2024.h1(), Year::new(2024).first_half());
+        assert_eq!(Year::new(2024).h2(), Year::new(2024).last_half());
     }
 }
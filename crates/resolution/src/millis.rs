@@ -0,0 +1,222 @@
+use crate::{
+    seconds::{self, parse_datetime},
+    FromMonotonic, Monotonic, SubDateResolution, TimeResolution,
+};
+#[cfg(feature = "serde")]
+use alloc::{format, string::String};
+use chrono::{DateTime, Duration, NaiveTime, Timelike, Utc};
+use core::{fmt, str};
+
+pub(crate) const FORMAT: &str = "%Y-%m-%d %H:%M:%S%.3f";
+
+/// Note that for sensible behaviour, the N chosen should be a number that divides into a second
+/// with no remainder (1, 2, 4, 5, 10, 20, 25, 50, 100, 200, 250, 500).
+///
+/// For periods of a second or longer, use `Seconds`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "Millis_", into = "Millis_"))]
+pub struct Millis<const N: u32> {
+    index: i64,
+}
+
+#[cfg(feature = "serde")]
+impl<const N: u32> TryFrom<Millis_> for Millis<N> {
+    type Error = String;
+    fn try_from(value: Millis_) -> Result<Self, Self::Error> {
+        if value.length == N {
+            Ok(Millis { index: value.index })
+        } else {
+            Err(format!(
+                "To create a Millis[Length:{}], the length field should be {} but was instead {}",
+                N, N, value.length
+            ))
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<const N: u32> From<Millis<N>> for Millis_ {
+    fn from(m: Millis<N>) -> Self {
+        Millis_ {
+            index: m.index,
+            length: N,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize, serde::Serialize)]
+pub(crate) struct Millis_ {
+    index: i64,
+    length: u32,
+}
+
+impl<const N: u32> From<DateTime<Utc>> for Millis<N> {
+    fn from(d: DateTime<Utc>) -> Self {
+        Millis {
+            index: d.timestamp_millis().div_euclid(i64::from(N)),
+        }
+    }
+}
+
+impl<const N: u32> str::FromStr for Millis<N> {
+    type Err = crate::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if N == 1 {
+            return Ok(parse_datetime(s, FORMAT)?.into());
+        }
+        let err = |input| crate::Error::ParseCustom {
+            ty_name: "Millis",
+            input,
+        };
+        let (start, end) = s
+            .split_once(" => ")
+            .ok_or_else(|| err(crate::error_text(s)))?;
+
+        let start = parse_datetime(start, FORMAT)?;
+        if (start.nanosecond() / 1_000_000).rem_euclid(N) != 0 {
+            return Err(err(crate::error_text(format_args!(
+                "Invalid start for Millis[Length:{}]: {}",
+                N, start
+            ))));
+        }
+        let end = parse_datetime(end, FORMAT)?;
+        if start + Duration::milliseconds(i64::from(N)) != end {
+            return Err(err(crate::error_text(format_args!(
+                "Invalid start-end combination for Millis[Length:{}]: {}",
+                N, s
+            ))));
+        }
+
+        Ok(start.into())
+    }
+}
+
+pub(crate) fn format_datetime(n: DateTime<Utc>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    seconds::format_datetime(n, f)?;
+    write!(f, ".{:03}", n.timestamp_subsec_millis())
+}
+
+impl<const N: u32> fmt::Display for Millis<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if N == 1 {
+            format_datetime(self.start_datetime(), f)
+        } else {
+            format_datetime(self.start_datetime(), f)?;
+            f.write_str(" => ")?;
+            format_datetime(self.succ().start_datetime(), f)
+        }
+    }
+}
+
+impl<const N: u32> crate::TimeResolution for Millis<N> {
+    fn succ_n(&self, n: u64) -> Millis<N> {
+        Millis {
+            index: self.index + i64::try_from(n).unwrap(),
+        }
+    }
+    fn pred_n(&self, n: u64) -> Millis<N> {
+        Millis {
+            index: self.index - i64::try_from(n).unwrap(),
+        }
+    }
+    fn start_datetime(&self) -> DateTime<Utc> {
+        DateTime::<Utc>::from_timestamp_millis(self.index * i64::from(N)).expect("valid timestamp")
+    }
+    fn fmt_name(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Millis[Length:{}]", N)
+    }
+}
+
+impl<const N: u32> Monotonic for Millis<N> {
+    fn to_monotonic(&self) -> i64 {
+        self.index
+    }
+    fn between(&self, other: Self) -> i64 {
+        other.index - self.index
+    }
+}
+
+impl<const N: u32> FromMonotonic for Millis<N> {
+    fn from_monotonic(index: i64) -> Self {
+        Millis { index }
+    }
+}
+
+impl<const N: u32> SubDateResolution for Millis<N> {
+    fn occurs_on_date(&self) -> chrono::NaiveDate {
+        self.start_datetime().date_naive()
+    }
+    fn first_on_day(day: chrono::NaiveDate, _params: Self::Params) -> Self {
+        day.and_time(NaiveTime::MIN).and_utc().into()
+    }
+
+    type Params = ();
+
+    fn params(&self) -> Self::Params {}
+
+    fn from_utc_datetime(datetime: DateTime<Utc>, _params: Self::Params) -> Self {
+        datetime.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Second, TimeRange};
+    use alloc::string::ToString;
+
+    fn datetime(s: u32, milli: u32) -> DateTime<Utc> {
+        chrono::NaiveDate::from_ymd_opt(2021, 1, 1)
+            .unwrap()
+            .and_hms_milli_opt(10, 5, s, milli)
+            .unwrap()
+            .and_utc()
+    }
+
+    #[test]
+    fn test_parse() {
+        let m = "2021-01-01 10:05:03.250".parse::<Millis<1>>().unwrap();
+        assert_eq!(m, datetime(3, 250).into());
+        assert_eq!(m.succ().to_string(), "2021-01-01 10:05:03.251");
+
+        let m = "2021-01-01 10:05:03.250 => 2021-01-01 10:05:03.500"
+            .parse::<Millis<250>>()
+            .unwrap();
+        assert_eq!(m.start_datetime(), datetime(3, 250));
+        assert_eq!(m.succ().start_datetime(), datetime(3, 500));
+        assert_eq!(
+            m.pred().to_string(),
+            "2021-01-01 10:05:03.000 => 2021-01-01 10:05:03.250"
+        );
+
+        assert!("2021-01-01 10:05:03.100 => 2021-01-01 10:05:03.350"
+            .parse::<Millis<250>>()
+            .is_err());
+        assert!("2021-01-01 10:05".parse::<Millis<1>>().is_err());
+    }
+
+    #[test]
+    fn test_rescale() {
+        let sec = Second::from(datetime(3, 0));
+        let millis = TimeRange::from_bounds(sec, sec).rescale::<Millis<100>>();
+        assert_eq!(millis.len().get(), 10);
+        assert_eq!(millis.start().start_datetime(), datetime(3, 0));
+        assert_eq!(millis.end().start_datetime(), datetime(3, 900));
+
+        // before the epoch
+        let early = Millis::<100>::from(DateTime::<Utc>::from_timestamp_millis(-50).unwrap());
+        assert_eq!(early.start_datetime().timestamp_millis(), -100);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_roundtrip() {
+        let m = Millis::<250>::from(datetime(3, 600));
+        assert_eq!(
+            m,
+            serde_json::from_str(&serde_json::to_string(&m).unwrap()).unwrap()
+        );
+    }
+}
@@ -2,17 +2,17 @@ use core::fmt::Debug;
 use core::num::NonZeroU64;
 
 use crate::{Error, FromMonotonic, Monotonic, SubDateResolution, TimeResolution};
-use alloc::{
-    fmt, format, str,
-    string::{String, ToString},
-};
+#[cfg(feature = "serde")]
+use alloc::{format, string::String};
 use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveTime, Timelike, Utc};
+use core::{fmt, str};
 
 const NUM_SECS: i64 = 60;
 
 /// Note that for sensible behaviour, the N chosen should be a number that either:
 /// 1. divides into an hour with no remainder (1, 2, 3, 4, 5, 6, 10, 12, 15, 20, 30, 60)
 /// 2. is exactly a whole number of hours that divides into a day with no remainder (60, 120, 180, 240, 360, 480, 1800)
+///
 /// Any other choice will result in unexpected / unuseful behaviour (eg the `Minutes` not cleanly fitting into parts of a day)
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
@@ -27,6 +27,7 @@ pub struct Minutes<const N: u32> {
 //     index: i64,
 // }
 
+#[cfg(feature = "serde")]
 impl<const N: u32> TryFrom<Minutes_> for Minutes<N> {
     type Error = String;
     fn try_from(value: Minutes_) -> Result<Self, Self::Error> {
@@ -41,6 +42,7 @@ impl<const N: u32> TryFrom<Minutes_> for Minutes<N> {
     }
 }
 
+#[cfg(feature = "serde")]
 impl<const N: u32> From<Minutes<N>> for Minutes_ {
     fn from(w: Minutes<N>) -> Self {
         Minutes_ {
@@ -50,7 +52,8 @@ impl<const N: u32> From<Minutes<N>> for Minutes_ {
     }
 }
 
-#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize, serde::Serialize)]
 pub(crate) struct Minutes_ {
     index: i64,
     pub(crate) length: u32,
@@ -72,7 +75,7 @@ impl<const N: u32> str::FromStr for Minutes<N> {
             if time.second() != 0 {
                 Err(crate::Error::ParseCustom {
                     ty_name: "Minutes",
-                    input: s.into(),
+                    input: crate::error_text(s),
                 })
             } else {
                 Ok(time.into())
@@ -82,12 +85,12 @@ impl<const N: u32> str::FromStr for Minutes<N> {
 
             let start = splits.next().ok_or_else(|| crate::Error::ParseCustom {
                 ty_name: "Minutes",
-                input: s.into(),
+                input: crate::error_text(s),
             })?;
 
             let end = splits.next().ok_or_else(|| crate::Error::ParseCustom {
                 ty_name: "Minutes",
-                input: s.into(),
+                input: crate::error_text(s),
             })?;
 
             let start = parse_datetime(start)?;
@@ -95,7 +98,10 @@ impl<const N: u32> str::FromStr for Minutes<N> {
             if (start.hour() * 60 + start.minute()).rem_euclid(N) != 0 {
                 return Err(crate::Error::ParseCustom {
                     ty_name: "Minutes",
-                    input: format!("Invalid start for Minutes[Length:{}]: {}", N, start,),
+                    input: crate::error_text(format_args!(
+                        "Invalid start for Minutes[Length:{}]: {}",
+                        N, start,
+                    )),
                 });
             }
             let end = parse_datetime(end)?;
@@ -103,10 +109,10 @@ impl<const N: u32> str::FromStr for Minutes<N> {
             if start + Duration::minutes(i64::from(N)) != end {
                 return Err(crate::Error::ParseCustom {
                     ty_name: "Minutes",
-                    input: format!(
+                    input: crate::error_text(format_args!(
                         "Invalid start-end combination for Minutes[Length:{}]: {}",
                         N, s
-                    ),
+                    )),
                 });
             }
 
@@ -116,10 +122,10 @@ impl<const N: u32> str::FromStr for Minutes<N> {
 }
 
 // TODO: make this more efficient
-fn format_datetime(n: DateTime<Utc>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+pub(crate) fn format_datetime(n: DateTime<Utc>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     write!(
         f,
-        "{}-{:02}-{:02} {:02}:{:02}",
+        "{:04}-{:02}-{:02} {:02}:{:02}",
         n.year(),
         n.month(),
         n.day(),
@@ -128,34 +134,43 @@ fn format_datetime(n: DateTime<Utc>, f: &mut fmt::Formatter<'_>) -> fmt::Result
     )
 }
 
-fn parse_datetime(input: &str) -> Result<DateTime<Utc>, Error> {
+pub(crate) fn parse_datetime(input: &str) -> Result<DateTime<Utc>, Error> {
+    if input.len() != 16 || !input.is_ascii() {
+        return Err(Error::UnexpectedInputLength {
+            required: 16,
+            actual: input.len(),
+            format: "%Y-%m-%d %H:%M",
+        });
+    }
     let year = input[0..=3]
         .parse()
-        .map_err(|e| Error::ParseIntDetailed(e, input[0..=3].to_string()))?;
+        .map_err(|e| Error::ParseIntDetailed(e, crate::error_text(&input[0..=3])))?;
     let month = input[5..=6]
         .parse()
-        .map_err(|e| Error::ParseIntDetailed(e, input[5..=6].to_string()))?;
+        .map_err(|e| Error::ParseIntDetailed(e, crate::error_text(&input[5..=6])))?;
     let day = input[8..=9]
         .parse()
-        .map_err(|e| Error::ParseIntDetailed(e, input[8..=9].to_string()))?;
+        .map_err(|e| Error::ParseIntDetailed(e, crate::error_text(&input[8..=9])))?;
     let hour = input[11..=12]
         .parse()
-        .map_err(|e| Error::ParseIntDetailed(e, input[10..=12].to_string()))?;
+        .map_err(|e| Error::ParseIntDetailed(e, crate::error_text(&input[10..=12])))?;
     let minute = input[14..=15]
         .parse()
-        .map_err(|e| Error::ParseIntDetailed(e, input[14..=15].to_string()))?;
+        .map_err(|e| Error::ParseIntDetailed(e, crate::error_text(&input[14..=15])))?;
 
     let date =
         NaiveDate::from_ymd_opt(year, month, day).ok_or_else(|| Error::ParseDateInternal {
-            message: alloc::format!("Invalid values for ymd: {year}-{month}-{day}"),
-            input: input.to_string(),
+            message: crate::error_text(format_args!(
+                "Invalid values for ymd: {year}-{month}-{day}"
+            )),
+            input: crate::error_text(input),
             format: "%Y/%m/%d %H:%M",
         })?;
 
     let time =
         NaiveTime::from_hms_opt(hour, minute, 0).ok_or_else(|| Error::ParseDateInternal {
-            message: alloc::format!("Invalid values for hm: {hour}:{minute}"),
-            input: input.to_string(),
+            message: crate::error_text(format_args!("Invalid values for hm: {hour}:{minute}")),
+            input: crate::error_text(input),
             format: "%Y/%m/%d %H:%M",
         })?;
 
@@ -190,8 +205,8 @@ impl<const N: u32> crate::TimeResolution for Minutes<N> {
         DateTime::<Utc>::from_timestamp(self.index * NUM_SECS * i64::from(N), 0)
             .expect("valid timestamp")
     }
-    fn name(&self) -> String {
-        format!("Minutes[Length:{}]", N)
+    fn fmt_name(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Minutes[Length:{}]", N)
     }
 }
 
@@ -1,10 +1,11 @@
 use crate::{day::DayOfMonth, DateResolution, DateResolutionExt, Day};
-use alloc::{
-    fmt, format, str,
-    string::{String, ToString},
-};
+#[cfg(feature = "serde")]
+use alloc::string::{String, ToString};
 use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, Utc};
-use core::{convert::TryFrom, result};
+use core::convert::TryFrom;
+#[cfg(feature = "serde")]
+use core::result;
+use core::{fmt, str};
 #[cfg(feature = "serde")]
 use serde::de;
 
@@ -48,7 +49,7 @@ fn month_num_from_name(name: &str) -> Result<u32, crate::Error> {
         n => {
             return Err(crate::Error::ParseCustom {
                 ty_name: "Month",
-                input: format!("Unknown month name `{}`", n),
+                input: crate::error_text(format_args!("Unknown month name `{}`", n)),
             })
         }
     };
@@ -79,16 +80,21 @@ impl str::FromStr for Month {
         let month =
             month_num_from_name(split.next().ok_or_else(|| crate::Error::ParseCustom {
                 ty_name: "Month",
-                input: s.to_string(),
+                input: crate::error_text(s),
             })?)?;
         let year = split
             .next()
             .ok_or_else(|| crate::Error::ParseCustom {
                 ty_name: "Month",
-                input: s.to_string(),
+                input: crate::error_text(s),
             })?
             .parse()?;
-        let date = chrono::NaiveDate::from_ymd_opt(year, month, 1).expect("valid datetime");
+        let date = chrono::NaiveDate::from_ymd_opt(year, month, 1).ok_or_else(|| {
+            crate::Error::ParseCustom {
+                ty_name: "Month",
+                input: crate::error_text(s),
+            }
+        })?;
         Ok(date.into())
     }
 }
@@ -107,8 +113,8 @@ impl crate::TimeResolution for Month {
         self.start().and_time(NaiveTime::MIN).and_utc()
     }
 
-    fn name(&self) -> String {
-        "Month".to_string()
+    fn fmt_name(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Month")
     }
 }
 
@@ -257,6 +263,14 @@ mod tests {
             "Jan-2021".parse::<Month>().unwrap().succ().pred().start(),
             chrono::NaiveDate::from_ymd_opt(2021, 1, 1).unwrap(),
         );
+
+        assert!(matches!(
+            "Jan-999999".parse::<Month>(),
+            Err(crate::Error::ParseCustom {
+                ty_name: "Month",
+                ..
+            })
+        ));
     }
 
     #[test]
@@ -0,0 +1,233 @@
+use crate::{TimeRange, TimeResolution};
+use alloc::vec::Vec;
+use chrono::{DateTime, Utc};
+
+/// The unit in which an `Overlap` is measured
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OverlapBasis {
+    /// Whole days, for resolutions of a day or longer
+    Days,
+    /// Whole seconds, for resolutions of a second or longer
+    Seconds,
+    /// Milliseconds, for any resolution
+    Millis,
+}
+
+impl OverlapBasis {
+    fn measure(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> i64 {
+        match self {
+            OverlapBasis::Days => (end.date_naive() - start.date_naive()).num_days(),
+            OverlapBasis::Seconds => (end - start).num_seconds(),
+            OverlapBasis::Millis => (end - start).num_milliseconds(),
+        }
+    }
+    fn unit(&self) -> &'static str {
+        match self {
+            OverlapBasis::Days => "days",
+            OverlapBasis::Seconds => "seconds",
+            OverlapBasis::Millis => "milliseconds",
+        }
+    }
+}
+
+/// `Overlap` is the part of an input `period` which falls within an output period, as
+/// returned by `TimeRange::overlaps`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overlap<P: TimeResolution> {
+    period: P,
+    overlap: i64,
+    length: i64,
+}
+
+impl<P: TimeResolution> Overlap<P> {
+    pub fn period(&self) -> P {
+        self.period
+    }
+    /// The length of the overlap
+    pub fn overlap(&self) -> i64 {
+        self.overlap
+    }
+    /// The length of the input period
+    pub fn length(&self) -> i64 {
+        self.length
+    }
+    /// The share of the input period which overlaps the output period, which is the share of
+    /// a value for the input period to apportion to the output period
+    pub fn fraction(&self) -> f64 {
+        self.overlap as f64 / self.length as f64
+    }
+    #[cfg(feature = "decimal")]
+    pub fn decimal_fraction(&self) -> rust_decimal::Decimal {
+        rust_decimal::Decimal::from(self.overlap) / rust_decimal::Decimal::from(self.length)
+    }
+}
+
+impl<P: TimeResolution> TimeRange<P> {
+    /// For each period in the range, the periods of `In` which overlap it, along with how much
+    /// of each overlaps. `containing` gives the period of `In` which contains a datetime, such
+    /// as `TypedResolution::containing`.
+    ///
+    /// Unlike `rescale`, this works between resolutions which don't nest, such as `Week`s and
+    /// `Month`s, so that values can be apportioned from one to the other.
+    ///
+    /// Returns an error if the periods of `In` are shorter than a whole unit of the `basis`,
+    /// such as `Hour`s measured in `OverlapBasis::Days`.
+    pub fn overlaps<'a, In, F>(
+        &'a self,
+        containing: F,
+        basis: OverlapBasis,
+    ) -> Result<impl Iterator<Item = (P, Vec<Overlap<In>>)> + 'a, crate::Error>
+    where
+        In: TimeResolution + 'a,
+        F: Fn(DateTime<Utc>) -> In + 'a,
+    {
+        let first = containing(self.start().start_datetime());
+        if basis.measure(first.start_datetime(), first.succ().start_datetime()) <= 0 {
+            return Err(crate::Error::UnmeasurableOverlap {
+                resolution: crate::error_text(first.display_name()),
+                basis: basis.unit(),
+            });
+        }
+        Ok(self.iter().map(move |out| {
+            let (start, end) = (out.start_datetime(), out.succ().start_datetime());
+            let mut overlaps = Vec::new();
+            let mut input = containing(start);
+            while input.start_datetime() < end {
+                let (input_start, input_end) =
+                    (input.start_datetime(), input.succ().start_datetime());
+                overlaps.push(Overlap {
+                    period: input,
+                    overlap: basis.measure(input_start.max(start), input_end.min(end)),
+                    length: basis.measure(input_start, input_end),
+                });
+                input = input.succ();
+            }
+            (out, overlaps)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::date;
+    use crate::{
+        BusinessDay, Calendar, DateResolution, Day, Hour, Millis, Minutes, Monday, Month,
+        TypedResolution, Week,
+    };
+
+    #[test]
+    fn test_weeks_to_months() {
+        let months = TimeRange::from_bounds(
+            Month::from_parts(2024, chrono::Month::January).unwrap(),
+            Month::from_parts(2024, chrono::Month::February).unwrap(),
+        );
+        let overlaps = months
+            .overlaps(Week::<Monday>::containing, OverlapBasis::Days)
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert_eq!(overlaps.len(), 2);
+
+        let (jan, weeks) = &overlaps[0];
+        assert_eq!(jan.start(), date(2024, 1, 1));
+        assert_eq!(weeks.len(), 5);
+        assert_eq!(weeks[0].period().start(), date(2024, 1, 1));
+        // the week starting on the 29th of January is split with February
+        let split = weeks[4];
+        assert_eq!((split.overlap(), split.length()), (3, 7));
+        assert_eq!(weeks.iter().map(Overlap::overlap).sum::<i64>(), 31);
+
+        let (_, weeks) = &overlaps[1];
+        assert_eq!(weeks[0].period(), split.period());
+        assert_eq!(weeks[0].overlap(), 4);
+        assert!((weeks[0].fraction() + split.fraction() - 1.0).abs() < f64::EPSILON);
+        assert_eq!(weeks.iter().map(Overlap::overlap).sum::<i64>(), 29);
+
+        // the seconds basis agrees for whole days
+        let by_seconds = months
+            .overlaps(Week::<Monday>::containing, OverlapBasis::Seconds)
+            .unwrap()
+            .next()
+            .unwrap()
+            .1;
+        assert_eq!(by_seconds[4].overlap(), 3 * 86400);
+    }
+
+    #[test]
+    fn test_sub_date() {
+        let hour = Hour::from(date(2024, 1, 1).and_hms_opt(10, 0, 0).unwrap().and_utc());
+        let (_, overlaps) = TimeRange::from_bounds(hour, hour)
+            .overlaps(Minutes::<40>::containing, OverlapBasis::Seconds)
+            .unwrap()
+            .next()
+            .unwrap();
+        assert_eq!(
+            overlaps
+                .iter()
+                .map(|o| (o.overlap() / 60, o.length() / 60))
+                .collect::<Vec<_>>(),
+            [(40, 40), (20, 40)]
+        );
+
+        // a day within a month
+        let day = Day::new(date(2024, 2, 10));
+        let (_, overlaps) = TimeRange::from_bounds(day, day)
+            .overlaps(Month::containing, OverlapBasis::Days)
+            .unwrap()
+            .next()
+            .unwrap();
+        assert_eq!((overlaps[0].overlap(), overlaps[0].length()), (1, 29));
+    }
+
+    #[test]
+    fn test_basis() {
+        let second = crate::Second::from(date(2024, 1, 1).and_hms_opt(10, 0, 0).unwrap().and_utc());
+        let seconds = TimeRange::from_bounds(second, second);
+        assert!(matches!(
+            seconds.overlaps(Millis::<250>::containing, OverlapBasis::Seconds),
+            Err(crate::Error::UnmeasurableOverlap {
+                basis: "seconds",
+                ..
+            })
+        ));
+        let (_, overlaps) = seconds
+            .overlaps(Millis::<250>::containing, OverlapBasis::Millis)
+            .unwrap()
+            .next()
+            .unwrap();
+        assert_eq!(overlaps.len(), 4);
+        assert!(overlaps.iter().all(|o| o.fraction() == 1.0));
+
+        let day = Day::new(date(2024, 1, 1));
+        assert!(TimeRange::from_bounds(day, day)
+            .overlaps(Hour::containing, OverlapBasis::Days)
+            .is_err());
+    }
+
+    #[test]
+    fn test_business_days() {
+        let calendar = Calendar::saturday_sunday([]);
+        let week = Week::<Monday>::from(date(2024, 1, 29));
+        let (_, overlaps) = TimeRange::from_bounds(week, week)
+            .overlaps(
+                |dt: DateTime<Utc>| BusinessDay::preceding(dt.date_naive(), &calendar),
+                OverlapBasis::Days,
+            )
+            .unwrap()
+            .next()
+            .unwrap();
+        assert_eq!(
+            overlaps
+                .iter()
+                .map(|o| (o.period().start(), o.overlap(), o.length()))
+                .collect::<Vec<_>>(),
+            [
+                (date(2024, 1, 29), 1, 1),
+                (date(2024, 1, 30), 1, 1),
+                (date(2024, 1, 31), 1, 1),
+                (date(2024, 2, 1), 1, 1),
+                (date(2024, 2, 2), 3, 3),
+            ]
+        );
+    }
+}
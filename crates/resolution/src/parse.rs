@@ -0,0 +1,314 @@
+use crate::{
+    Day, DynPeriod, DynResolution, HalfYear, HalfYearNumber, IsoWeek, Millisecond, Minute, Month,
+    Quarter, QuarterNumber, Second, Year,
+};
+use chrono::{Datelike, NaiveDate, NaiveDateTime};
+use core::num::NonZeroU32;
+
+type Parser = fn(&str) -> Option<DynPeriod>;
+
+// formats are attempted in order, so where one format would also accept the input of
+// another, the more specific format must come first
+const FORMATS: &[(&str, Parser)] = &[
+    ("<resolution>:<period>", |s| s.parse().ok()),
+    ("%Y", |s| Some(Year::new(year(s)?).into())),
+    ("Q%q-%Y", |s| {
+        let (q, y) = s.split_once('-')?;
+        quarter(y, q.strip_prefix('Q')?)
+    }),
+    ("%YQ%q", |s| {
+        let (y, q) = s.split_once('Q')?;
+        quarter(y, q)
+    }),
+    ("%Y-Q%q", |s| {
+        let (y, q) = s.split_once("-Q")?;
+        quarter(y, q)
+    }),
+    ("H%h-%Y", |s| {
+        let (h, y) = s.split_once('-')?;
+        half_year(y, h.strip_prefix('H')?)
+    }),
+    ("%YH%h", |s| {
+        let (y, h) = s.split_once('H')?;
+        half_year(y, h)
+    }),
+    ("%Y-H%h", |s| {
+        let (y, h) = s.split_once("-H")?;
+        half_year(y, h)
+    }),
+    ("%b-%Y", |s| {
+        let (m, y) = s.split_once('-')?;
+        month(y, m)
+    }),
+    ("%B %Y", |s| {
+        let (m, y) = s.split_once(' ')?;
+        month(y, m)
+    }),
+    ("%Y-%m", |s| {
+        let (y, m) = s.split_once('-')?;
+        if m.len() != 2 {
+            return None;
+        }
+        let m = chrono::Month::try_from(u8::try_from(m.parse::<u32>().ok()?).ok()?).ok()?;
+        Some(Month::from_year_month(year(y)?, m).into())
+    }),
+    ("%G-W%V", |s| {
+        s.contains("-W")
+            .then(|| s.parse::<IsoWeek>().ok())
+            .flatten()
+            .map(Into::into)
+    }),
+    ("%GW%V", |s| {
+        (s.contains('W') && !s.contains('-'))
+            .then(|| s.parse::<IsoWeek>().ok())
+            .flatten()
+            .map(Into::into)
+    }),
+    ("%Y-%m-%d", |s| Some(Day::new(date(s, "%Y-%m-%d")?).into())),
+    ("Week starting %Y-%m-%d", |s| {
+        let start = date(s.strip_prefix("Week starting ")?, "%Y-%m-%d")?;
+        let resolution = DynResolution::Week(start.weekday());
+        Some(resolution.period(start.and_time(chrono::NaiveTime::MIN).and_utc()))
+    }),
+    ("%Y-%m-%dT%H:%M", |s| minute(s, "%Y-%m-%dT%H:%M")),
+    ("%Y-%m-%d %H:%M", |s| minute(s, "%Y-%m-%d %H:%M")),
+    ("%Y-%m-%dT%H:%M:%S", |s| second(s, "%Y-%m-%dT%H:%M:%S")),
+    ("%Y-%m-%d %H:%M:%S", |s| second(s, "%Y-%m-%d %H:%M:%S")),
+    ("%Y-%m-%dT%H:%M:%S%.3f", |s| {
+        millisecond(s, "%Y-%m-%dT%H:%M:%S%.3f")
+    }),
+    ("%Y-%m-%d %H:%M:%S%.3f", |s| {
+        millisecond(s, "%Y-%m-%d %H:%M:%S%.3f")
+    }),
+    ("<start> => <end>", |s| {
+        let (start, end) = s.split_once(" => ")?;
+        let (start, end) = [
+            "%Y-%m-%d %H:%M",
+            "%Y-%m-%d %H:%M:%S",
+            "%Y-%m-%d %H:%M:%S%.3f",
+        ]
+        .into_iter()
+        .find_map(|f| Some((datetime(start, f)?, datetime(end, f)?)))?;
+        let length = u32::try_from((end - start).num_milliseconds()).ok()?;
+        let resolution = if length % 60_000 == 0 {
+            DynResolution::Minutes(NonZeroU32::new(length / 60_000)?)
+        } else if length % 1_000 == 0 {
+            DynResolution::Seconds(NonZeroU32::new(length / 1_000)?)
+        } else {
+            DynResolution::Millis(NonZeroU32::new(length)?)
+        };
+        Some(DynPeriod::new(resolution, resolution.parse_index(s).ok()?))
+    }),
+];
+
+// the names of the `FORMATS`, for the error when none of them match
+const FORMAT_NAMES: [&str; FORMATS.len()] = {
+    let mut names = [""; FORMATS.len()];
+    let mut idx = 0;
+    while idx < FORMATS.len() {
+        names[idx] = FORMATS[idx].0;
+        idx += 1;
+    }
+    names
+};
+
+fn year(s: &str) -> Option<i16> {
+    (s.len() == 4 && s.bytes().all(|b| b.is_ascii_digit()))
+        .then(|| s.parse().ok())
+        .flatten()
+}
+
+fn quarter(y: &str, q: &str) -> Option<DynPeriod> {
+    let q = match q {
+        "1" => QuarterNumber::Q1,
+        "2" => QuarterNumber::Q2,
+        "3" => QuarterNumber::Q3,
+        "4" => QuarterNumber::Q4,
+        _ => return None,
+    };
+    Some(Quarter::from_parts(year(y)?, q).into())
+}
+
+fn half_year(y: &str, h: &str) -> Option<DynPeriod> {
+    let h = match h {
+        "1" => HalfYearNumber::H1,
+        "2" => HalfYearNumber::H2,
+        _ => return None,
+    };
+    Some(HalfYear::from_parts(year(y)?, h).into())
+}
+
+fn month(y: &str, m: &str) -> Option<DynPeriod> {
+    // `chrono::Month` parses both the abbreviated and full names, without regard to case
+    Some(Month::from_year_month(year(y)?, m.parse().ok()?).into())
+}
+
+fn date(s: &str, format: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(s, format).ok()
+}
+
+fn datetime(s: &str, format: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(s, format).ok()
+}
+
+fn minute(s: &str, format: &str) -> Option<DynPeriod> {
+    Some(Minute::from(datetime(s, format)?.and_utc()).into())
+}
+
+fn second(s: &str, format: &str) -> Option<DynPeriod> {
+    Some(Second::from(datetime(s, format)?.and_utc()).into())
+}
+
+fn millisecond(s: &str, format: &str) -> Option<DynPeriod> {
+    Some(Millisecond::from(datetime(s, format)?.and_utc()).into())
+}
+
+/// Parse a period, inferring its resolution from the format of the `input`.
+///
+/// This accepts the `Display` format of each resolution which doesn't require any
+/// further parameters, as well as some common alternatives such as `2024Q3`, `2024-07` or
+/// `2024-07-15T10:30`. Fiscal resolutions are not inferred, as their start month can't be
+/// determined from the input.
+///
+/// If no format matches, the error lists each of the formats which were attempted.
+pub fn parse_period(input: &str) -> Result<DynPeriod, crate::Error> {
+    let trimmed = input.trim();
+    FORMATS
+        .iter()
+        .find_map(|(_, parser)| parser(trimmed))
+        .ok_or_else(|| crate::Error::ParseUnrecognisedPeriod {
+            input: crate::error_text(input),
+            attempted: &FORMAT_NAMES,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FiveMinute, Minutes, Seconds, TimeResolution, Week};
+    use alloc::{format, string::ToString};
+
+    fn dt(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_inferred() {
+        let q3 = DynPeriod::from(Quarter::from_parts(2024, QuarterNumber::Q3));
+        for input in [
+            "Q3-2024",
+            "2024Q3",
+            "2024-Q3",
+            " Q3-2024 ",
+            "Quarter:Q3-2024",
+        ] {
+            assert_eq!(parse_period(input).unwrap(), q3, "{input}");
+        }
+        let jul = DynPeriod::from(Month::from(dt(2024, 7, 1)));
+        for input in ["Jul-2024", "2024-07", "July 2024", "jul-2024"] {
+            assert_eq!(parse_period(input).unwrap(), jul, "{input}");
+        }
+        let h2 = DynPeriod::from(HalfYear::from(dt(2024, 7, 1)));
+        for input in ["H2-2024", "2024H2", "2024-H2"] {
+            assert_eq!(parse_period(input).unwrap(), h2, "{input}");
+        }
+        let wk = DynPeriod::from(IsoWeek::from_parts(2024, 5).unwrap());
+        for input in ["2024-W05", "2024W05"] {
+            assert_eq!(parse_period(input).unwrap(), wk, "{input}");
+        }
+
+        assert_eq!(
+            parse_period("2024").unwrap(),
+            DynPeriod::from(Year::new(2024))
+        );
+        assert_eq!(
+            parse_period("2024-07-15").unwrap(),
+            DynPeriod::from(Day::new(dt(2024, 7, 15)))
+        );
+        assert_eq!(
+            parse_period("Week starting 2024-07-14").unwrap(),
+            DynPeriod::from(Week::<crate::Sunday>::new(dt(2024, 7, 14)))
+        );
+
+        let datetime = dt(2024, 7, 15)
+            .and_hms_milli_opt(10, 30, 15, 250)
+            .unwrap()
+            .and_utc();
+        assert_eq!(
+            parse_period("2024-07-15T10:30").unwrap(),
+            DynPeriod::from(Minute::from(datetime))
+        );
+        assert_eq!(
+            parse_period("2024-07-15 10:30:15").unwrap(),
+            DynPeriod::from(Second::from(datetime))
+        );
+        assert_eq!(
+            parse_period("2024-07-15T10:30:15.250").unwrap(),
+            DynPeriod::from(Millisecond::from(datetime))
+        );
+        assert_eq!(
+            parse_period("2024-07-15 10:30 => 2024-07-15 10:35").unwrap(),
+            DynPeriod::from(FiveMinute::from(datetime))
+        );
+        assert_eq!(
+            parse_period("2024-07-15 10:30:12 => 2024-07-15 10:30:16").unwrap(),
+            DynPeriod::from(Seconds::<4>::from(datetime))
+        );
+        assert!(parse_period("2024-07-15 10:31 => 2024-07-15 10:36").is_err());
+    }
+
+    #[test]
+    fn test_display_roundtrip() {
+        let datetime = dt(2024, 7, 15).and_hms_opt(10, 30, 0).unwrap().and_utc();
+        let periods = [
+            DynPeriod::from(Year::from(dt(2024, 7, 15))),
+            DynPeriod::from(Quarter::from(dt(2024, 7, 15))),
+            DynPeriod::from(HalfYear::from(dt(2024, 7, 15))),
+            DynPeriod::from(Month::from(dt(2024, 7, 15))),
+            DynPeriod::from(IsoWeek::from(dt(2024, 7, 15))),
+            DynPeriod::from(Week::<crate::Wednesday>::from(dt(2024, 7, 15))),
+            DynPeriod::from(Day::new(dt(2024, 7, 15))),
+            DynPeriod::from(Minutes::<30>::from(datetime)),
+            DynPeriod::from(Minute::from(datetime)),
+            DynPeriod::from(Second::from(datetime)),
+        ];
+        for period in periods {
+            let display = period.to_string();
+            let typed_display = display
+                .strip_prefix(&format!("{}:", period.resolution()))
+                .unwrap();
+            assert_eq!(parse_period(typed_display).unwrap(), period);
+            assert_eq!(parse_period(&period.to_string()).unwrap(), period);
+            assert_eq!(parse_period(typed_display).unwrap().succ(), period.succ());
+        }
+    }
+
+    #[test]
+    fn test_diagnostics() {
+        let err = parse_period("Q5-2024").unwrap_err();
+        let crate::Error::ParseUnrecognisedPeriod { input, attempted } = &err else {
+            panic!("unexpected error {err:?}");
+        };
+        assert_eq!(input, "Q5-2024");
+        assert!(attempted.contains(&"Q%q-%Y"));
+        assert!(attempted.contains(&"%Y-%m-%d"));
+        assert!(format!("{err}").contains("Q%q-%Y, %YQ%q"));
+
+        assert!(parse_period("20245").is_err());
+        assert!(parse_period("2024-13").is_err());
+        assert!(parse_period("").is_err());
+
+        // periods named with their resolution are parsed without panicking
+        for input in [
+            "Quarter:Q5-2024",
+            "Quarter:Q0-2024",
+            "Quarter:Q12-2024",
+            "Quarter:-2024",
+            "Quarter:é-2024",
+            "Month:Jan-999999",
+            "Year:999999",
+        ] {
+            assert!(parse_period(input).is_err(), "{input}");
+        }
+    }
+}
@@ -1,11 +1,11 @@
-use crate::{month, year, DateResolution, DateResolutionExt, Year};
-use alloc::{
-    fmt, str,
-    string::{String, ToString},
-    vec::Vec,
-};
+use crate::{month, year, DateResolution, DateResolutionExt, HalfYear, Year};
+#[cfg(feature = "serde")]
+use alloc::string::{String, ToString};
 use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, Utc};
-use core::{convert::TryFrom, result};
+use core::convert::TryFrom;
+#[cfg(feature = "serde")]
+use core::result;
+use core::{fmt, str};
 #[cfg(feature = "serde")]
 use serde::de;
 
@@ -23,8 +23,8 @@ impl crate::TimeResolution for Quarter {
         self.start().and_time(NaiveTime::MIN).and_utc()
     }
 
-    fn name(&self) -> String {
-        "Quarter".to_string()
+    fn fmt_name(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Quarter")
     }
 }
 
@@ -81,6 +81,9 @@ impl Quarter {
     pub fn last_month(&self) -> month::Month {
         self.end().into()
     }
+    pub fn half_year(&self) -> HalfYear {
+        self.start().into()
+    }
     pub fn year(&self) -> year::Year {
         super::Year::new(self.year_num())
     }
@@ -106,7 +109,7 @@ pub enum QuarterNumber {
 }
 
 impl QuarterNumber {
-    fn offset(&self) -> i64 {
+    pub(crate) fn offset(&self) -> i64 {
         match self {
             QuarterNumber::Q1 => 0,
             QuarterNumber::Q2 => 1,
@@ -122,6 +125,57 @@ impl fmt::Display for Quarter {
     }
 }
 
+#[cfg(feature = "serde")]
+impl<'de> de::Deserialize<'de> for Quarter {
+    fn deserialize<D>(deserializer: D) -> result::Result<Quarter, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        let date = s.parse::<Quarter>().map_err(serde::de::Error::custom)?;
+        Ok(date)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Quarter {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let s = self.to_string();
+        serializer.serialize_str(&s)
+    }
+}
+
+impl str::FromStr for Quarter {
+    type Err = crate::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(parsed) = chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+            Ok(parsed.into())
+        } else {
+            let err = || crate::Error::ParseCustom {
+                ty_name: "Quarter",
+                input: crate::error_text(s),
+            };
+            let mut split = s.split('-');
+            let (Some(qtr), Some(year), None) = (split.next(), split.next(), split.next()) else {
+                return Err(err());
+            };
+            let qtr = match qtr {
+                "Q1" => 1,
+                "Q2" => 2,
+                "Q3" => 3,
+                "Q4" => 4,
+                _ => return Err(err()),
+            };
+            let year = year.parse()?;
+            let date = chrono::NaiveDate::from_ymd_opt(year, qtr * 3 - 2, 1).ok_or_else(err)?;
+            Ok(date.into())
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -156,6 +210,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_invalid() {
+        for input in [
+            "Q5-2024",
+            "Q0-2024",
+            "Q12-2024",
+            "-2024",
+            "é-2024",
+            "Q1-999999",
+        ] {
+            let Err(crate::Error::ParseCustom { ty_name, .. }) = input.parse::<Quarter>() else {
+                panic!("{input} should be rejected");
+            };
+            assert_eq!(ty_name, "Quarter");
+        }
+    }
+
     #[test]
     fn test_parse_date_syntax() {
         assert_eq!(
@@ -201,57 +272,3 @@ mod tests {
         );
     }
 }
-
-#[cfg(feature = "serde")]
-impl<'de> de::Deserialize<'de> for Quarter {
-    fn deserialize<D>(deserializer: D) -> result::Result<Quarter, D::Error>
-    where
-        D: de::Deserializer<'de>,
-    {
-        let s = String::deserialize(deserializer)?;
-        let date = s.parse::<Quarter>().map_err(serde::de::Error::custom)?;
-        Ok(date)
-    }
-}
-
-#[cfg(feature = "serde")]
-impl serde::Serialize for Quarter {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
-        let s = self.to_string();
-        serializer.serialize_str(&s)
-    }
-}
-
-impl str::FromStr for Quarter {
-    type Err = crate::Error;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if let Ok(parsed) = chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d") {
-            Ok(parsed.into())
-        } else {
-            let split = s
-                .split('-')
-                .map(ToString::to_string)
-                .collect::<Vec<String>>();
-            if split.len() == 2 {
-                let qtr = split[0]
-                    .chars()
-                    .nth(1)
-                    .unwrap()
-                    .to_string()
-                    .parse::<u32>()?;
-                let year = split[1].parse()?;
-                let date =
-                    chrono::NaiveDate::from_ymd_opt(year, qtr * 3 - 2, 1).expect("valid date");
-                Ok(date.into())
-            } else {
-                Err(crate::Error::ParseCustom {
-                    ty_name: "Quarter",
-                    input: s.to_string(),
-                })
-            }
-        }
-    }
-}
@@ -1,10 +1,15 @@
+#[cfg(feature = "alloc")]
+use crate::{BusinessCalendar, BusinessDay, FromMonotonic};
 use crate::{
-    DateResolution, DateResolutionExt, FixedTimeZone, FromMonotonic, LongerThanOrEqual,
+    DateResolution, DateResolutionExt, FixedTimeZone, LongerThanOrEqual, Rescalable,
     SubDateResolution, TimeResolution, Zoned,
 };
-use alloc::{collections, fmt, vec::Vec};
+#[cfg(feature = "alloc")]
+use alloc::{collections, vec::Vec};
 use chrono::{DateTime, Utc};
-use core::{iter::FusedIterator, mem, num};
+#[cfg(feature = "alloc")]
+use core::mem;
+use core::{iter::FusedIterator, num};
 #[cfg(feature = "serde")]
 use serde::de;
 
@@ -15,6 +20,11 @@ use serde::de;
 /// This is useful to represent the time axis of a timeseries.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize),
+    rkyv(crate = rkyv)
+)]
 pub struct TimeRange<P: TimeResolution> {
     #[cfg_attr(
         feature = "serde",
@@ -24,11 +34,32 @@ pub struct TimeRange<P: TimeResolution> {
     len: num::NonZeroU64,
 }
 
+#[cfg(feature = "rkyv")]
+impl<P> ArchivedTimeRange<P>
+where
+    P: TimeResolution + rkyv::Archive,
+{
+    pub fn start(&self) -> &rkyv::Archived<P> {
+        &self.start
+    }
+    pub fn len(&self) -> num::NonZeroU64 {
+        self.len
+            .get()
+            .try_into()
+            .expect("archived length is non-zero")
+    }
+}
+
+/// How a `TimeRange` relates to another, as returned by `TimeRange::compare`
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TimeRangeComparison {
+    /// The range extends both before and after the other range
     Superset,
+    /// The range is entirely within, or equal to, the other range
     Subset,
+    /// The range extends before, but not after, the other range. They may or may not overlap.
     Earlier,
+    /// The range extends after, but not before, the other range. They may or may not overlap.
     Later,
 }
 
@@ -46,8 +77,20 @@ impl<P: DateResolution> TimeRange<P> {
         // do from_start_end and expect it
         TimeRange::from_bounds(first_start, last_end)
     }
+
+    /// The business days within this range, or `None` if there are none
+    #[cfg(feature = "alloc")]
+    pub fn to_business_days<C>(&self, calendar: C) -> Option<TimeRange<BusinessDay<C>>>
+    where
+        C: BusinessCalendar,
+    {
+        let first = BusinessDay::following(self.start.start(), calendar);
+        let last = BusinessDay::preceding(self.end().end(), calendar);
+        (first <= last).then(|| TimeRange::from_bounds(first, last))
+    }
 }
 
+#[cfg(feature = "alloc")]
 impl<P: TimeResolution + FromMonotonic> TimeRange<P> {
     pub fn from_map(map: collections::BTreeSet<i64>) -> Vec<TimeRange<P>> {
         let mut ranges = Vec::new();
@@ -82,16 +125,19 @@ impl<P: TimeResolution + FromMonotonic> TimeRange<P> {
 
             prev = val;
         }
+        ranges.push(current_range);
 
         ranges
     }
 }
 
 impl<P: TimeResolution> TimeRange<P> {
+    #[cfg(feature = "alloc")]
     pub fn to_indexes(&self) -> collections::BTreeSet<i64> {
         self.iter().map(|p| p.to_monotonic()).collect()
     }
 
+    #[cfg(feature = "alloc")]
     pub fn from_set(set: &collections::BTreeSet<P>) -> Option<TimeRange<P>> {
         if u32::try_from(set.len()).is_err() {
             return None;
@@ -162,26 +208,23 @@ impl<P: TimeResolution> TimeRange<P> {
         }
     }
 
-    // pub fn subtract(&self, other: &TimeRange<P>) -> (Option<TimeRange<P>>, Option<TimeRange<P>>) {
-    //     (
-    //         {
-
-    //             Some(TimeRange::from_bounds(self.start(), other.start().pred().min(self.end())))
-    //         },
-    //         {
-    //             Some(TimeRange::from_bounds(other.end().succ().max(self.start()), self.end()))
-    //         },
-    //     )
-    // }
-
-    // pub fn compare(&self, other: &TimeRange<P>) -> TimeRangeComparison {
-    //     match self.subtract(other) {
-    //         (Some(_), Some(_)) => TimeRangeComparison::Superset,
-    //         (Some(_), None) => TimeRangeComparison::Earlier,
-    //         (None, Some(_)) => TimeRangeComparison::Later,
-    //         (None, None) => TimeRangeComparison::Subset,
-    //     }
-    // }
+    /// The parts of this range which are before and after `other` respectively
+    pub fn subtract(&self, other: &TimeRange<P>) -> (Option<TimeRange<P>>, Option<TimeRange<P>>) {
+        let before = (self.start() < other.start())
+            .then(|| TimeRange::from_bounds(self.start(), other.start().pred().min(self.end())));
+        let after = (self.end() > other.end())
+            .then(|| TimeRange::from_bounds(other.end().succ().max(self.start()), self.end()));
+        (before, after)
+    }
+
+    pub fn compare(&self, other: &TimeRange<P>) -> TimeRangeComparison {
+        match self.subtract(other) {
+            (Some(_), Some(_)) => TimeRangeComparison::Superset,
+            (Some(_), None) => TimeRangeComparison::Earlier,
+            (None, Some(_)) => TimeRangeComparison::Later,
+            (None, None) => TimeRangeComparison::Subset,
+        }
+    }
 
     pub fn start(&self) -> P {
         self.start
@@ -194,20 +237,16 @@ impl<P: TimeResolution> TimeRange<P> {
         O: TimeResolution,
         P: LongerThanOrEqual<O>,
     {
-        extern crate std;
-        use std::dbg;
-
         let range_start = self.start.start_datetime();
         let range_end = self.end().succ().start_datetime();
 
         let comparison_start = rhs.start_datetime();
         let comparison_end = rhs.succ().start_datetime();
 
-        dbg!(range_start, range_end, comparison_start, comparison_end);
-
         (range_start..range_end).contains(&comparison_start)
             && (range_start..range_end).contains(&comparison_end)
     }
+    #[cfg(feature = "alloc")]
     pub fn set(&self) -> collections::BTreeSet<P> {
         self.iter().collect()
     }
@@ -218,9 +257,19 @@ impl<P: TimeResolution> TimeRange<P> {
         }
     }
 
+    /// The range of `Out` covering the same time as this range. This is only available between
+    /// resolutions whose periods line up, as described by `Rescalable`, so that `Minutes<15>`
+    /// can be rescaled to `Minutes<5>` but not to `Minutes<10>`:
+    ///
+    /// ```compile_fail
+    /// # use resolution::{Minutes, TimeRange};
+    /// # let start = Minutes::<15>::from(chrono::DateTime::UNIX_EPOCH);
+    /// TimeRange::from_bounds(start, start).rescale::<Minutes<10>>();
+    /// ```
     pub fn rescale<Out>(&self) -> TimeRange<Out>
     where
         Out: TimeResolution + From<DateTime<Utc>>,
+        P: Rescalable<Out>,
     {
         // get the exact start
         let start = Out::from(self.start().start_datetime());
@@ -281,98 +330,33 @@ impl<P: TimeResolution, Z: FixedTimeZone> TimeRange<Zoned<P, Z>> {
     }
 }
 
-pub struct Cache<K: Ord + fmt::Debug + Copy, T: Send + fmt::Debug + Eq + Copy> {
-    // The actual data in the cache
-    data: collections::BTreeMap<K, T>,
-    // The requests for data which has been cached
-    requests: collections::BTreeSet<K>,
-}
-
-// merge a request into a set of requests, grouping contigious on the way
-fn missing_pieces<K: Ord + fmt::Debug + Copy>(
-    request: collections::BTreeSet<K>,
-    requests: &collections::BTreeSet<K>,
-) -> Vec<collections::BTreeSet<K>> {
-    let mut to_request = Vec::new();
-    let mut current_request = collections::BTreeSet::new();
-
-    // there is a fundamental assumption that `request` is contigious
-    // as long as `request` is contigious, each of the returned requests
-    // will also be contigious
-    // there is no need to worry about filling gaps to reduce the total number
-    // of requests - the consumer will handle this
-    for requested in request {
-        if !requests.contains(&requested) {
-            current_request.insert(requested);
-        } else if !current_request.is_empty() {
-            to_request.push(mem::take(&mut current_request));
-        }
-    }
-
-    if !current_request.is_empty() {
-        to_request.push(current_request);
-    }
-
-    to_request
-}
-
-// No concept of partial, becuse we will simply request the missing data, then ask the cache again.
-pub enum CacheResponse<K: Ord + fmt::Debug + Copy, T: Send + fmt::Debug + Eq + Copy> {
-    Hit(collections::BTreeMap<K, T>), // means the whole request as able to be replied, doesn't necessarily mean the whole range of data is filled
-    Miss(Vec<collections::BTreeSet<K>>), // will be a minimal reasonable set of time ranges to request from the provider
-}
-
-impl<K: Ord + fmt::Debug + Copy, T: Send + fmt::Debug + Eq + Copy> Cache<K, T> {
-    pub fn get(&self, request: collections::BTreeSet<K>) -> CacheResponse<K, T> {
-        if request.is_empty() {
-            CacheResponse::Hit(collections::BTreeMap::new())
-        } else if self.requests.is_superset(&request) {
-            CacheResponse::Hit(
-                self.data
-                    .iter()
-                    // mustn't be empty othewise we would have returned out of the first arm of the `if`
-                    .filter(|(k, _)| request.iter().next().unwrap() <= *k)
-                    .filter(|(k, _)| request.iter().next_back().unwrap() >= *k)
-                    .map(|(k, v)| (*k, *v))
-                    .collect(),
-            )
-        } else {
-            CacheResponse::Miss(missing_pieces(request, &self.requests))
-        }
-    }
-    pub fn empty() -> Cache<K, T> {
-        Cache {
-            data: collections::BTreeMap::new(),
-            requests: collections::BTreeSet::new(),
-        }
-    }
-    // could also store versioned data, with a DateTIme<Utc> associated with each T at each P?
-    // or allow overwriting, etc
-    // but this default seems better for now
-    pub fn add(
-        &mut self,
-        mut request_range: collections::BTreeSet<K>,
-        data: collections::BTreeMap<K, T>,
-    ) {
-        self.requests.append(&mut request_range);
-        for (point, datum) in data {
-            // should we check if the data point already exists?
-            // if it does exist, what should we do?
-            // for now, ignoring, as otherwise
-            // this function would need to be fallible
-            self.data.insert(point, datum);
-        }
-    }
-}
 #[cfg(test)]
 mod tests {
-    use alloc::string::ToString;
     use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
 
-    use crate::{Day, FiveMinute, Hour, Minutes, Month, Year};
+    use crate::{days, Day, FiveMinute, Hour, Minutes, Month, Year};
 
     use super::*;
 
+    #[test]
+    fn test_subtract_compare() {
+        let range = days(10, 20);
+        assert_eq!(
+            range.subtract(&days(12, 15)),
+            (Some(days(10, 11)), Some(days(16, 20)))
+        );
+        assert_eq!(range.subtract(&days(0, 30)), (None, None));
+        assert_eq!(range.subtract(&days(30, 40)), (Some(range), None));
+        assert_eq!(range.subtract(&days(15, 40)), (Some(days(10, 14)), None));
+        assert_eq!(range.subtract(&days(0, 12)), (None, Some(days(13, 20))));
+
+        assert_eq!(range.compare(&days(12, 15)), TimeRangeComparison::Superset);
+        assert_eq!(range.compare(&days(10, 20)), TimeRangeComparison::Subset);
+        assert_eq!(range.compare(&days(15, 40)), TimeRangeComparison::Earlier);
+        assert_eq!(range.compare(&days(30, 40)), TimeRangeComparison::Earlier);
+        assert_eq!(range.compare(&days(0, 12)), TimeRangeComparison::Later);
+    }
+
     #[test]
     fn test_iter() {
         let mth = Month::from_parts(2024, chrono::Month::January).unwrap();
@@ -390,36 +374,12 @@ mod tests {
         assert_eq!(iter.next(), None);
     }
 
-    #[test]
-    fn test_missing_pieces() {
-        let pieces = missing_pieces(
-            collections::BTreeSet::from([1, 2, 3, 4, 5, 6, 7, 8, 9, 10]),
-            &collections::BTreeSet::from([2, 3, 7, 8]),
-        );
-        assert_eq!(
-            pieces,
-            Vec::from([
-                collections::BTreeSet::from([1]),
-                collections::BTreeSet::from([4, 5, 6]),
-                collections::BTreeSet::from([9, 10]),
-            ])
-        )
-    }
     #[test]
     fn test_contains() {
-        extern crate std;
-        use std::dbg;
-
         let mth = Month::from_parts(2024, chrono::Month::January).unwrap();
 
         let day_range = mth.rescale::<Day>();
 
-        dbg!(
-            mth.to_string(),
-            day_range.start.start(),
-            day_range.end().start()
-        );
-
         assert!(day_range.contains(Minutes::<5>::from_utc_datetime(
             NaiveDateTime::new(
                 NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
@@ -462,5 +422,29 @@ mod tests {
         assert_eq!(fiveminute.rescale::<Month>(), months);
         assert_eq!(hours.rescale::<Month>(), months);
         assert_eq!(days.rescale::<Month>(), months);
+
+        let quarter_hours = year.rescale::<Minutes<15>>();
+        let five_minutes = quarter_hours.rescale::<Minutes<5>>();
+        assert_eq!(five_minutes, fiveminute);
+        assert_eq!(five_minutes.rescale::<Minutes<15>>(), quarter_hours);
+        let four_hours = quarter_hours.rescale::<Minutes<240>>();
+        assert_eq!(four_hours.len().get(), 366 * 6);
+        assert_eq!(four_hours.rescale::<Minutes<1440>>().len().get(), 366);
+    }
+
+    #[test]
+    fn test_rescale_zoned() {
+        let hour = Zoned::<Hour, Utc>::from(
+            NaiveDate::from_ymd_opt(2024, 1, 1)
+                .unwrap()
+                .and_hms_opt(10, 0, 0)
+                .unwrap()
+                .and_utc(),
+        );
+        let hours = TimeRange::from_bounds(hour, hour.succ());
+        let five_minutes = hours.rescale::<Zoned<FiveMinute, Utc>>();
+        assert_eq!(five_minutes.len().get(), 24);
+        assert_eq!(five_minutes.start().start_datetime(), hour.start_datetime());
+        assert_eq!(five_minutes.rescale::<Zoned<Hour, Utc>>(), hours);
     }
 }
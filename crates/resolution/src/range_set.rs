@@ -0,0 +1,269 @@
+use crate::{FromMonotonic, TimeRange, TimeResolution};
+use alloc::{collections, vec::Vec};
+
+/// `TimeRangeSet` stores a set of periods as a sorted sequence of disjoint `TimeRange`s.
+///
+/// Ranges which overlap or are adjacent are merged when they are added, so the set of ranges is
+/// always the minimal one covering the periods. This allows reasoning about coverage and gaps
+/// (eg in a timeseries) without storing each period individually.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimeRangeSet<P: TimeResolution> {
+    ranges: Vec<TimeRange<P>>,
+}
+
+impl<P: TimeResolution> TimeRangeSet<P> {
+    pub fn empty() -> TimeRangeSet<P> {
+        TimeRangeSet { ranges: Vec::new() }
+    }
+    /// The disjoint ranges, in order
+    pub fn ranges(&self) -> &[TimeRange<P>] {
+        &self.ranges
+    }
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+    /// The total number of periods in the set
+    pub fn len(&self) -> u64 {
+        self.ranges.iter().map(|r| r.len().get()).sum()
+    }
+    /// The range from the first to the last period in the set, including any gaps
+    pub fn span(&self) -> Option<TimeRange<P>> {
+        Some(TimeRange::from_bounds(
+            self.ranges.first()?.start(),
+            self.ranges.last()?.end(),
+        ))
+    }
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = P> + '_ {
+        self.ranges.iter().flat_map(|r| r.iter())
+    }
+
+    // the index of the first range which doesn't end before `period`
+    fn position(&self, period: P) -> usize {
+        self.ranges.partition_point(|r| r.end() < period)
+    }
+
+    pub fn contains(&self, period: P) -> bool {
+        self.ranges
+            .get(self.position(period))
+            .is_some_and(|r| r.start() <= period)
+    }
+    /// Whether every period of the `range` is in the set
+    pub fn contains_range(&self, range: &TimeRange<P>) -> bool {
+        self.ranges
+            .get(self.position(range.start()))
+            .is_some_and(|r| r.start() <= range.start() && r.end() >= range.end())
+    }
+
+    pub fn insert(&mut self, range: TimeRange<P>) {
+        let mut merged = range;
+        let mut ranges = Vec::with_capacity(self.ranges.len() + 1);
+        let mut inserted = false;
+        for r in self.ranges.drain(..) {
+            if r.end() < merged.start() && r.end().succ() != merged.start() {
+                ranges.push(r);
+            } else if r.start() > merged.end() && merged.end().succ() != r.start() {
+                if !inserted {
+                    ranges.push(merged);
+                    inserted = true;
+                }
+                ranges.push(r);
+            } else {
+                merged = TimeRange::from_bounds(
+                    r.start().min(merged.start()),
+                    r.end().max(merged.end()),
+                );
+            }
+        }
+        if !inserted {
+            ranges.push(merged);
+        }
+        self.ranges = ranges;
+    }
+
+    pub fn remove(&mut self, range: TimeRange<P>) {
+        let mut ranges = Vec::with_capacity(self.ranges.len() + 1);
+        for r in self.ranges.drain(..) {
+            if r.intersection(&range).is_none() {
+                ranges.push(r);
+            } else {
+                let (before, after) = r.subtract(&range);
+                ranges.extend(before);
+                ranges.extend(after);
+            }
+        }
+        self.ranges = ranges;
+    }
+
+    pub fn union(&self, other: &TimeRangeSet<P>) -> TimeRangeSet<P> {
+        let mut union = self.clone();
+        for r in &other.ranges {
+            union.insert(*r);
+        }
+        union
+    }
+
+    pub fn intersection(&self, other: &TimeRangeSet<P>) -> TimeRangeSet<P> {
+        let mut ranges = Vec::new();
+        let (mut lhs, mut rhs) = (
+            self.ranges.iter().peekable(),
+            other.ranges.iter().peekable(),
+        );
+        while let (Some(l), Some(r)) = (lhs.peek(), rhs.peek()) {
+            ranges.extend(l.intersection(r));
+            if l.end() < r.end() {
+                lhs.next();
+            } else {
+                rhs.next();
+            }
+        }
+        // as both inputs are normalized, the intersections are disjoint and not adjacent
+        TimeRangeSet { ranges }
+    }
+
+    /// The periods which are in this set but not in `other`
+    pub fn difference(&self, other: &TimeRangeSet<P>) -> TimeRangeSet<P> {
+        let mut difference = self.clone();
+        for r in &other.ranges {
+            difference.remove(*r);
+        }
+        difference
+    }
+
+    /// The periods within the range `within` which are not in the set, ie the gaps
+    ///
+    /// This only visits the ranges which overlap `within`, so is proportional to the number of
+    /// gaps rather than the number of periods.
+    pub fn complement(&self, within: TimeRange<P>) -> TimeRangeSet<P> {
+        let mut ranges = Vec::new();
+        let mut start = Some(within.start());
+        for r in self.ranges[self.position(within.start())..]
+            .iter()
+            .take_while(|r| r.start() <= within.end())
+        {
+            let Some(s) = start else {
+                break;
+            };
+            if r.start() > s {
+                ranges.push(TimeRange::from_bounds(s, r.start().pred()));
+            }
+            start = (r.end() < within.end()).then(|| r.end().succ());
+        }
+        ranges.extend(start.map(|s| TimeRange::from_bounds(s, within.end())));
+        TimeRangeSet { ranges }
+    }
+}
+
+impl<P: TimeResolution + FromMonotonic> TimeRangeSet<P> {
+    pub fn from_indexes(indexes: collections::BTreeSet<i64>) -> TimeRangeSet<P> {
+        // `from_map` returns ordered ranges that are disjoint and not adjacent
+        TimeRangeSet {
+            ranges: TimeRange::from_map(indexes),
+        }
+    }
+}
+
+impl<P: TimeResolution> Default for TimeRangeSet<P> {
+    fn default() -> Self {
+        TimeRangeSet::empty()
+    }
+}
+
+impl<P: TimeResolution> From<TimeRange<P>> for TimeRangeSet<P> {
+    fn from(range: TimeRange<P>) -> Self {
+        TimeRangeSet {
+            ranges: Vec::from([range]),
+        }
+    }
+}
+
+impl<P: TimeResolution> FromIterator<TimeRange<P>> for TimeRangeSet<P> {
+    fn from_iter<I: IntoIterator<Item = TimeRange<P>>>(iter: I) -> Self {
+        let mut set = TimeRangeSet::empty();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<P: TimeResolution> Extend<TimeRange<P>> for TimeRangeSet<P> {
+    fn extend<I: IntoIterator<Item = TimeRange<P>>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{days, Day};
+
+    #[test]
+    fn test_normalized() {
+        let set = [
+            days(10, 12),
+            days(20, 25),
+            days(13, 14),
+            days(0, 2),
+            days(24, 30),
+        ]
+        .into_iter()
+        .collect::<TimeRangeSet<_>>();
+        assert_eq!(set.ranges(), [days(0, 2), days(10, 14), days(20, 30)]);
+        assert_eq!(set.len(), 19);
+        assert_eq!(set.span(), Some(days(0, 30)));
+        assert_eq!(set.iter().count(), 19);
+
+        let mut merged = set.clone();
+        merged.insert(days(1, 21));
+        assert_eq!(merged.ranges(), [days(0, 30)]);
+
+        assert_eq!(
+            TimeRangeSet::<Day>::from_indexes(collections::BTreeSet::from([
+                0, 1, 2, 10, 11, 12, 13, 14
+            ])),
+            [days(0, 2), days(10, 14)].into_iter().collect()
+        );
+    }
+
+    #[test]
+    fn test_contains() {
+        let set = [days(0, 2), days(10, 14)]
+            .into_iter()
+            .collect::<TimeRangeSet<_>>();
+        assert!(set.contains(Day::from_monotonic(0)));
+        assert!(set.contains(Day::from_monotonic(12)));
+        assert!(!set.contains(Day::from_monotonic(5)));
+        assert!(!set.contains(Day::from_monotonic(15)));
+        assert!(!set.contains(Day::from_monotonic(-1)));
+        assert!(set.contains_range(&days(11, 14)));
+        assert!(!set.contains_range(&days(1, 10)));
+        assert!(!TimeRangeSet::empty().contains(Day::from_monotonic(0)));
+    }
+
+    #[test]
+    fn test_operations() {
+        let lhs = [days(0, 10), days(20, 30)]
+            .into_iter()
+            .collect::<TimeRangeSet<_>>();
+        let rhs = [days(5, 22), days(28, 40)]
+            .into_iter()
+            .collect::<TimeRangeSet<_>>();
+
+        assert_eq!(lhs.union(&rhs).ranges(), [days(0, 40)]);
+        assert_eq!(
+            lhs.intersection(&rhs).ranges(),
+            [days(5, 10), days(20, 22), days(28, 30)]
+        );
+        assert_eq!(lhs.difference(&rhs).ranges(), [days(0, 4), days(23, 27)]);
+        assert_eq!(rhs.difference(&lhs).ranges(), [days(11, 19), days(31, 40)]);
+        assert_eq!(
+            lhs.complement(days(-5, 35)).ranges(),
+            [days(-5, -1), days(11, 19), days(31, 35)]
+        );
+        assert!(lhs.complement(days(0, 10)).is_empty());
+
+        let mut removed = lhs.clone();
+        removed.remove(days(2, 25));
+        assert_eq!(removed.ranges(), [days(0, 1), days(26, 30)]);
+    }
+}
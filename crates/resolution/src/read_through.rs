@@ -0,0 +1,410 @@
+use crate::{RangeCache, RangeCacheResponse, TimeRange, TimeRangeSet, TimeResolution};
+use alloc::{boxed::Box, collections, fmt, vec::Vec};
+use core::{future::Future, pin::Pin, task};
+use std::sync::Mutex;
+
+/// `ReadThroughCache` wraps a `RangeCache` around a function which fetches the data for a range.
+///
+/// Requests are served from the cache where possible, and otherwise the missing parts of the
+/// request are fetched, added to the cache, and the full request is served. When several
+/// requests are in progress at once, a range which is already being fetched for one request
+/// is not fetched again for another, instead the other request waits for it. Separate missing
+/// parts of a request are fetched concurrently.
+pub struct ReadThroughCache<P, T, F>
+where
+    P: TimeResolution,
+    T: Send + fmt::Debug + Eq + Copy,
+{
+    fetch: F,
+    state: Mutex<State<P, T>>,
+}
+
+struct State<P: TimeResolution, T: Send + fmt::Debug + Eq + Copy> {
+    cache: RangeCache<P, T>,
+    in_flight: Vec<InFlight<P>>,
+    next_id: u64,
+}
+
+// a range currently being fetched, along with the requests waiting for it, by their id
+struct InFlight<P: TimeResolution> {
+    id: u64,
+    range: TimeRange<P>,
+    waiting: Vec<(u64, task::Waker)>,
+}
+
+impl<P: TimeResolution, T: Send + fmt::Debug + Eq + Copy> State<P, T> {
+    fn next_id(&mut self) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+    fn finish(&mut self, id: u64) {
+        if let Some(i) = self.in_flight.iter().position(|f| f.id == id) {
+            for (_, waker) in self.in_flight.swap_remove(i).waiting {
+                waker.wake();
+            }
+        }
+    }
+}
+
+impl<P, T, F, Fut, E> ReadThroughCache<P, T, F>
+where
+    P: TimeResolution,
+    T: Send + fmt::Debug + Eq + Copy,
+    F: Fn(TimeRange<P>) -> Fut,
+    Fut: Future<Output = Result<collections::BTreeMap<P, T>, E>>,
+{
+    pub fn new(fetch: F) -> ReadThroughCache<P, T, F> {
+        ReadThroughCache::with_cache(RangeCache::empty(), fetch)
+    }
+    pub fn with_cache(cache: RangeCache<P, T>, fetch: F) -> ReadThroughCache<P, T, F> {
+        ReadThroughCache {
+            fetch,
+            state: Mutex::new(State {
+                cache,
+                in_flight: Vec::new(),
+                next_id: 0,
+            }),
+        }
+    }
+    /// Remove the cached data within the `range`, so that it is fetched again
+    pub fn invalidate(&self, range: TimeRange<P>) {
+        self.state.lock().unwrap().cache.invalidate(range);
+    }
+    /// Get the data for the `request`, fetching any parts which are not already cached.
+    ///
+    /// If a fetch fails, the error is returned and nothing from that fetch is cached.
+    pub async fn get(&self, request: TimeRange<P>) -> Result<collections::BTreeMap<P, T>, E> {
+        let waiter = self.state.lock().unwrap().next_id();
+        loop {
+            let to_fetch = {
+                let mut state = self.state.lock().unwrap();
+                let missing = match state.cache.get(request) {
+                    RangeCacheResponse::Hit(data) => return Ok(data),
+                    RangeCacheResponse::Miss(missing) => missing,
+                };
+                // adjacent pieces are merged, and anything fetched by another request is left
+                let mut to_fetch = missing.into_iter().collect::<TimeRangeSet<_>>();
+                for in_flight in &state.in_flight {
+                    to_fetch.remove(in_flight.range);
+                }
+                let mut guard = Vec::with_capacity(to_fetch.ranges().len());
+                for range in to_fetch.ranges() {
+                    let id = state.next_id();
+                    state.in_flight.push(InFlight {
+                        id,
+                        range: *range,
+                        waiting: Vec::new(),
+                    });
+                    guard.push((id, *range));
+                }
+                InFlightGuard {
+                    state: &self.state,
+                    ranges: guard,
+                }
+            };
+
+            FetchAll {
+                state: &self.state,
+                fetches: to_fetch
+                    .ranges
+                    .iter()
+                    .map(|(id, range)| Fetch {
+                        id: *id,
+                        range: *range,
+                        future: Some(Box::pin((self.fetch)(*range))),
+                    })
+                    .collect(),
+            }
+            .await?;
+            drop(to_fetch);
+
+            Waiting {
+                state: &self.state,
+                request,
+                waiter,
+            }
+            .await;
+        }
+    }
+}
+
+// ensures the ranges are no longer in flight, even if fetching fails or the request is dropped,
+// so that other requests don't wait for them forever
+struct InFlightGuard<'a, P: TimeResolution, T: Send + fmt::Debug + Eq + Copy> {
+    state: &'a Mutex<State<P, T>>,
+    ranges: Vec<(u64, TimeRange<P>)>,
+}
+
+impl<P: TimeResolution, T: Send + fmt::Debug + Eq + Copy> Drop for InFlightGuard<'_, P, T> {
+    fn drop(&mut self) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        for (id, _) in &self.ranges {
+            state.finish(*id);
+        }
+    }
+}
+
+// fetches each of the ranges concurrently, adding each to the cache as soon as it arrives. The
+// first error is returned, and the remaining fetches are dropped.
+struct FetchAll<'a, P: TimeResolution, T: Send + fmt::Debug + Eq + Copy, Fut> {
+    state: &'a Mutex<State<P, T>>,
+    fetches: Vec<Fetch<P, Fut>>,
+}
+
+// an in flight range, and its fetch until it completes
+struct Fetch<P: TimeResolution, Fut> {
+    id: u64,
+    range: TimeRange<P>,
+    future: Option<Pin<Box<Fut>>>,
+}
+
+// the fetches are pinned in their own boxes, and nothing else is pinned
+impl<P: TimeResolution, T: Send + fmt::Debug + Eq + Copy, Fut> Unpin for FetchAll<'_, P, T, Fut> {}
+
+impl<P, T, Fut, E> Future for FetchAll<'_, P, T, Fut>
+where
+    P: TimeResolution,
+    T: Send + fmt::Debug + Eq + Copy,
+    Fut: Future<Output = Result<collections::BTreeMap<P, T>, E>>,
+{
+    type Output = Result<(), E>;
+    fn poll(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<Self::Output> {
+        let FetchAll { state, fetches } = self.get_mut();
+        for fetch in fetches.iter_mut() {
+            let Some(future) = &mut fetch.future else {
+                continue;
+            };
+            if let task::Poll::Ready(data) = future.as_mut().poll(cx) {
+                fetch.future = None;
+                let mut state = state.lock().unwrap();
+                state.cache.add(fetch.range, data?);
+                state.finish(fetch.id);
+            }
+        }
+        if fetches.iter().all(|fetch| fetch.future.is_none()) {
+            task::Poll::Ready(Ok(()))
+        } else {
+            task::Poll::Pending
+        }
+    }
+}
+
+// resolves once no range overlapping the request is being fetched
+struct Waiting<'a, P: TimeResolution, T: Send + fmt::Debug + Eq + Copy> {
+    state: &'a Mutex<State<P, T>>,
+    request: TimeRange<P>,
+    waiter: u64,
+}
+
+impl<P: TimeResolution, T: Send + fmt::Debug + Eq + Copy> Future for Waiting<'_, P, T> {
+    type Output = ();
+    fn poll(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<()> {
+        let mut state = self.state.lock().unwrap();
+        let mut pending = false;
+        for in_flight in &mut state.in_flight {
+            if in_flight.range.intersection(&self.request).is_some() {
+                // each request keeps one waker per range, replaced if it's polled with another
+                match in_flight
+                    .waiting
+                    .iter_mut()
+                    .find(|(id, _)| *id == self.waiter)
+                {
+                    Some((_, waker)) if waker.will_wake(cx.waker()) => {}
+                    Some((_, waker)) => waker.clone_from(cx.waker()),
+                    None => in_flight.waiting.push((self.waiter, cx.waker().clone())),
+                }
+                pending = true;
+            }
+        }
+        if pending {
+            task::Poll::Pending
+        } else {
+            task::Poll::Ready(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{days, Day, Monotonic};
+    use core::cell::{Cell, RefCell};
+    use core::future;
+    use std::{sync::Arc, task::Wake};
+
+    struct NoopWaker;
+
+    impl Wake for NoopWaker {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    // poll each of the futures until all are complete
+    fn join_all<F: Future>(futures: Vec<F>) -> Vec<F::Output> {
+        let waker = task::Waker::from(Arc::new(NoopWaker));
+        let mut cx = task::Context::from_waker(&waker);
+        let mut futures = futures.into_iter().map(Box::pin).collect::<Vec<_>>();
+        let mut outputs = futures.iter().map(|_| None).collect::<Vec<_>>();
+        while outputs.iter().any(Option::is_none) {
+            for (future, output) in futures.iter_mut().zip(&mut outputs) {
+                if output.is_none() {
+                    if let task::Poll::Ready(o) = future.as_mut().poll(&mut cx) {
+                        *output = Some(o);
+                    }
+                }
+            }
+        }
+        outputs.into_iter().map(Option::unwrap).collect()
+    }
+
+    // returns pending once, so that other requests are able to run while this is in flight
+    #[derive(Default)]
+    struct YieldOnce(bool);
+
+    impl Future for YieldOnce {
+        type Output = ();
+        fn poll(mut self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<()> {
+            if self.0 {
+                task::Poll::Ready(())
+            } else {
+                self.0 = true;
+                cx.waker().wake_by_ref();
+                task::Poll::Pending
+            }
+        }
+    }
+
+    #[test]
+    fn test_read_through() {
+        let fetched = RefCell::new(Vec::new());
+        let cache = ReadThroughCache::new(|range: TimeRange<Day>| {
+            fetched.borrow_mut().push(range);
+            async move {
+                YieldOnce::default().await;
+                Ok::<_, ()>(
+                    range
+                        .iter()
+                        .map(|d| (d, d.to_monotonic()))
+                        .collect::<collections::BTreeMap<_, _>>(),
+                )
+            }
+        });
+
+        let data = join_all(Vec::from([cache.get(days(0, 9))]))
+            .remove(0)
+            .unwrap();
+        assert_eq!(data.len(), 10);
+
+        // overlapping requests at the same time only fetch each missing range once
+        let results = join_all(Vec::from([
+            cache.get(days(5, 19)),
+            cache.get(days(12, 24)),
+            cache.get(days(0, 4)),
+        ]));
+        assert_eq!(results[0].as_ref().unwrap().len(), 15);
+        assert_eq!(results[1].as_ref().unwrap().len(), 13);
+        assert_eq!(results[2].as_ref().unwrap().len(), 5);
+        assert_eq!(*fetched.borrow(), [days(0, 9), days(10, 19), days(20, 24)]);
+
+        cache.invalidate(days(3, 6));
+        join_all(Vec::from([cache.get(days(0, 24))]))
+            .remove(0)
+            .unwrap();
+        assert_eq!(fetched.borrow().last(), Some(&days(3, 6)));
+    }
+
+    #[test]
+    fn test_concurrent_fetches() {
+        let events = RefCell::new(Vec::new());
+        let cache = ReadThroughCache::new(|range: TimeRange<Day>| {
+            let events = &events;
+            async move {
+                events.borrow_mut().push(("start", range));
+                YieldOnce::default().await;
+                events.borrow_mut().push(("end", range));
+                Ok::<_, ()>(range.iter().map(|d| (d, 0)).collect())
+            }
+        });
+        join_all(Vec::from([cache.get(days(5, 9))]))
+            .remove(0)
+            .unwrap();
+        events.borrow_mut().clear();
+
+        // the two missing parts either side of the cached days are fetched at the same time
+        let data = join_all(Vec::from([cache.get(days(0, 14))]))
+            .remove(0)
+            .unwrap();
+        assert_eq!(data.len(), 15);
+        assert_eq!(
+            *events.borrow(),
+            [
+                ("start", days(0, 4)),
+                ("start", days(10, 14)),
+                ("end", days(0, 4)),
+                ("end", days(10, 14)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_waiting_waker() {
+        let ready = Cell::new(false);
+        let cache = ReadThroughCache::new(|range: TimeRange<Day>| {
+            let ready = &ready;
+            async move {
+                future::poll_fn(|_| {
+                    if ready.get() {
+                        task::Poll::Ready(())
+                    } else {
+                        task::Poll::Pending
+                    }
+                })
+                .await;
+                Ok::<_, ()>(range.iter().map(|d| (d, 0)).collect())
+            }
+        });
+        let waker = task::Waker::from(Arc::new(NoopWaker));
+        let mut cx = task::Context::from_waker(&waker);
+
+        let mut first = Box::pin(cache.get(days(0, 4)));
+        let mut second = Box::pin(cache.get(days(2, 3)));
+        assert!(first.as_mut().poll(&mut cx).is_pending());
+        for _ in 0..10 {
+            assert!(second.as_mut().poll(&mut cx).is_pending());
+        }
+        // the waiting request holds a single waker, however often it's polled
+        assert_eq!(cache.state.lock().unwrap().in_flight[0].waiting.len(), 1);
+
+        ready.set(true);
+        assert_eq!(
+            first.as_mut().poll(&mut cx).map(|r| r.unwrap().len()),
+            task::Poll::Ready(5)
+        );
+        assert_eq!(
+            second.as_mut().poll(&mut cx).map(|r| r.unwrap().len()),
+            task::Poll::Ready(2)
+        );
+    }
+
+    #[test]
+    fn test_fetch_error() {
+        let attempts = RefCell::new(0);
+        let cache = ReadThroughCache::new(|range: TimeRange<Day>| {
+            *attempts.borrow_mut() += 1;
+            let attempt = *attempts.borrow();
+            async move {
+                YieldOnce::default().await;
+                if attempt == 1 {
+                    Err("unavailable")
+                } else {
+                    Ok(range.iter().map(|d| (d, 0)).collect())
+                }
+            }
+        });
+        let results = join_all(Vec::from([cache.get(days(0, 4)), cache.get(days(2, 3))]));
+        assert_eq!(results[0], Err("unavailable"));
+        // the waiting request fetches the range itself once the first fetch fails
+        assert_eq!(results[1].as_ref().unwrap().len(), 2);
+        assert_eq!(*attempts.borrow(), 2);
+    }
+}
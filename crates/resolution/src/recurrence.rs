@@ -0,0 +1,591 @@
+use crate::{BusinessCalendar, Day, DynPeriod, DynResolution, TimeResolution, TypedResolution};
+use alloc::{collections::VecDeque, fmt, str, vec::Vec};
+use chrono::{Datelike, NaiveDate, Weekday};
+use core::num::NonZeroU32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+impl Frequency {
+    fn resolution(&self) -> DynResolution {
+        match self {
+            Frequency::Daily => DynResolution::Day,
+            Frequency::Weekly => DynResolution::IsoWeek,
+            Frequency::Monthly => DynResolution::Month,
+            Frequency::Yearly => DynResolution::Year,
+        }
+    }
+    // the number of periods in the 400 years after which the Gregorian calendar repeats
+    fn periods_per_cycle(&self) -> u64 {
+        match self {
+            Frequency::Daily => 146_097,
+            Frequency::Weekly => 20_871,
+            Frequency::Monthly => 4_800,
+            Frequency::Yearly => 400,
+        }
+    }
+}
+
+/// The day or days within each period of a `Recurrence` on which it occurs
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RecurrenceDay {
+    /// The same day in each period as the start date, eg the same weekday for `Weekly` or the
+    /// same day of the month for `Monthly`. Periods without that day, such as the 31st for a
+    /// 30 day month, are skipped.
+    SameAsStart,
+    /// Each of the weekdays, for `Weekly` only
+    Weekdays(Vec<Weekday>),
+    /// The nth day of the month, or counting back from the end of the month when negative,
+    /// for `Monthly` only
+    MonthDay(i8),
+    /// The nth of the weekday in the period, or counting back from the end of the period when
+    /// negative, for `Monthly` or `Yearly`
+    NthWeekday(i8, Weekday),
+    /// The nth business day in the period, or counting back from the end of the period when
+    /// negative, for `Weekly`, `Monthly` or `Yearly`
+    NthBusinessDay(i8),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RecurrenceEnd {
+    Never,
+    /// After this many occurrences
+    Count(NonZeroU32),
+    /// After the last occurrence on or before this date
+    Until(NaiveDate),
+}
+
+/// `Recurrence` describes a schedule of days, such as every second month on the last business
+/// day, or weekly on Monday and Thursday until the end of the year.
+///
+/// This can be parsed from and displayed as the subset of iCalendar `RRULE` syntax which
+/// it supports, eg `FREQ=MONTHLY;INTERVAL=2;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Recurrence {
+    frequency: Frequency,
+    interval: NonZeroU32,
+    day: RecurrenceDay,
+    end: RecurrenceEnd,
+}
+
+impl Recurrence {
+    /// Occurs on the same day as the start in every period of the `frequency`, with no end
+    pub fn new(frequency: Frequency) -> Recurrence {
+        Recurrence {
+            frequency,
+            interval: NonZeroU32::MIN,
+            day: RecurrenceDay::SameAsStart,
+            end: RecurrenceEnd::Never,
+        }
+    }
+    /// Only occur in every `interval`th period
+    pub fn every(self, interval: NonZeroU32) -> Recurrence {
+        Recurrence { interval, ..self }
+    }
+    /// Returns an error if the `day` can't be used with the frequency, or is out of range
+    pub fn on(self, day: RecurrenceDay) -> Result<Recurrence, crate::Error> {
+        let reason = match (&day, self.frequency) {
+            (RecurrenceDay::SameAsStart, _) => None,
+            (RecurrenceDay::Weekdays(days), Frequency::Weekly) => days
+                .is_empty()
+                .then_some("at least one weekday is required"),
+            (RecurrenceDay::MonthDay(n), Frequency::Monthly) => {
+                (*n == 0 || n.unsigned_abs() > 31).then_some("month day must be within 1..=31")
+            }
+            (RecurrenceDay::NthWeekday(n, _), Frequency::Monthly) => {
+                (*n == 0 || n.unsigned_abs() > 5).then_some("weekday position must be within 1..=5")
+            }
+            (RecurrenceDay::NthWeekday(n, _), Frequency::Yearly) => (*n == 0
+                || n.unsigned_abs() > 53)
+                .then_some("weekday position must be within 1..=53"),
+            (RecurrenceDay::NthBusinessDay(n), Frequency::Weekly) => (*n == 0
+                || n.unsigned_abs() > 7)
+                .then_some("business day position must be within 1..=7"),
+            (RecurrenceDay::NthBusinessDay(n), Frequency::Monthly) => (*n == 0
+                || n.unsigned_abs() > 31)
+                .then_some("business day position must be within 1..=31"),
+            (RecurrenceDay::NthBusinessDay(n), Frequency::Yearly) => {
+                (*n == 0).then_some("business day position must not be zero")
+            }
+            _ => Some("day is not supported with this frequency"),
+        };
+        let recurrence = Recurrence { day, ..self };
+        match reason {
+            None => Ok(recurrence),
+            Some(reason) => Err(crate::Error::InvalidRecurrence {
+                rule: crate::error_text(recurrence),
+                reason,
+            }),
+        }
+    }
+    pub fn count(self, count: NonZeroU32) -> Recurrence {
+        Recurrence {
+            end: RecurrenceEnd::Count(count),
+            ..self
+        }
+    }
+    pub fn until(self, until: NaiveDate) -> Recurrence {
+        Recurrence {
+            end: RecurrenceEnd::Until(until),
+            ..self
+        }
+    }
+    pub fn frequency(&self) -> Frequency {
+        self.frequency
+    }
+    pub fn interval(&self) -> NonZeroU32 {
+        self.interval
+    }
+    pub fn day(&self) -> &RecurrenceDay {
+        &self.day
+    }
+    pub fn end(&self) -> RecurrenceEnd {
+        self.end
+    }
+
+    /// The days on which this occurs, from the `start` onwards. The `calendar` is only used
+    /// for `RecurrenceDay::NthBusinessDay`.
+    ///
+    /// The days end early if a full 400 year cycle of the Gregorian calendar passes without an
+    /// occurrence, such as for the 30th of the month every 12 months from a start in February,
+    /// as then there will never be another.
+    pub fn days<C: BusinessCalendar>(&self, start: NaiveDate, calendar: C) -> Occurrences<'_, C> {
+        Occurrences {
+            recurrence: self,
+            calendar,
+            start,
+            period: self
+                .frequency
+                .resolution()
+                .period(Day::new(start).start_datetime()),
+            pending: VecDeque::new(),
+            count: 0,
+            empty: 0,
+        }
+    }
+
+    // the periods visited repeat relative to the calendar after this many
+    fn cycle(&self) -> u64 {
+        let periods = self.frequency.periods_per_cycle();
+        let (mut a, mut b) = (periods, u64::from(self.interval.get()));
+        while b != 0 {
+            (a, b) = (b, a % b);
+        }
+        periods / a
+    }
+
+    /// The periods in which this occurs, from the `start` onwards. Where there are several
+    /// occurrences in the same period, the period is only returned once.
+    pub fn periods<'a, P: TypedResolution + 'a, C: BusinessCalendar + 'a>(
+        &'a self,
+        start: NaiveDate,
+        calendar: C,
+    ) -> impl Iterator<Item = P> + 'a {
+        let mut previous = None;
+        self.days(start, calendar).filter_map(move |day| {
+            let period = P::containing(day.start_datetime());
+            (previous.replace(period) != Some(period)).then_some(period)
+        })
+    }
+
+    // the days in the `period` which match, in order
+    fn matching<C: BusinessCalendar>(
+        &self,
+        period: DynPeriod,
+        start: NaiveDate,
+        calendar: C,
+    ) -> Vec<NaiveDate> {
+        let first = period.start_datetime().date_naive();
+        let next = period.succ().start_datetime().date_naive();
+        let dates = first.iter_days().take_while(|d| *d < next);
+        let nth = |dates: Vec<NaiveDate>, n: i8| {
+            let index = if n > 0 {
+                usize::from(n.unsigned_abs() - 1)
+            } else {
+                dates.len().checked_sub(usize::from(n.unsigned_abs()))?
+            };
+            dates.get(index).copied()
+        };
+        match &self.day {
+            RecurrenceDay::SameAsStart => match self.frequency {
+                Frequency::Daily => Vec::from([first]),
+                Frequency::Weekly => dates.filter(|d| d.weekday() == start.weekday()).collect(),
+                Frequency::Monthly => first.with_day(start.day()).into_iter().collect(),
+                Frequency::Yearly => {
+                    NaiveDate::from_ymd_opt(first.year(), start.month(), start.day())
+                        .into_iter()
+                        .collect()
+                }
+            },
+            RecurrenceDay::Weekdays(days) => {
+                dates.filter(|d| days.contains(&d.weekday())).collect()
+            }
+            RecurrenceDay::MonthDay(n) => nth(dates.collect(), *n).into_iter().collect(),
+            RecurrenceDay::NthWeekday(n, weekday) => {
+                nth(dates.filter(|d| d.weekday() == *weekday).collect(), *n)
+                    .into_iter()
+                    .collect()
+            }
+            RecurrenceDay::NthBusinessDay(n) => {
+                let calendar = calendar.calendar();
+                nth(dates.filter(|d| calendar.is_business_day(*d)).collect(), *n)
+                    .into_iter()
+                    .collect()
+            }
+        }
+    }
+}
+
+/// The days on which a `Recurrence` occurs
+pub struct Occurrences<'a, C: BusinessCalendar> {
+    recurrence: &'a Recurrence,
+    calendar: C,
+    start: NaiveDate,
+    // the next period to find occurrences in
+    period: DynPeriod,
+    pending: VecDeque<NaiveDate>,
+    count: u32,
+    // the number of consecutive periods without an occurrence
+    empty: u64,
+}
+
+impl<C: BusinessCalendar> Iterator for Occurrences<'_, C> {
+    type Item = Day;
+    fn next(&mut self) -> Option<Day> {
+        loop {
+            if let RecurrenceEnd::Count(count) = self.recurrence.end {
+                if self.count >= count.get() {
+                    return None;
+                }
+            }
+            let Some(date) = self.pending.pop_front() else {
+                if self.empty >= self.recurrence.cycle() {
+                    return None;
+                }
+                if let RecurrenceEnd::Until(until) = self.recurrence.end {
+                    if self.period.start_datetime().date_naive() > until {
+                        return None;
+                    }
+                }
+                self.pending = self
+                    .recurrence
+                    .matching(self.period, self.start, self.calendar)
+                    .into();
+                self.empty = if self.pending.is_empty() {
+                    self.empty + 1
+                } else {
+                    0
+                };
+                self.period = self
+                    .period
+                    .succ_n(u64::from(self.recurrence.interval.get()));
+                continue;
+            };
+            if date < self.start {
+                continue;
+            }
+            if let RecurrenceEnd::Until(until) = self.recurrence.end {
+                if date > until {
+                    return None;
+                }
+            }
+            self.count += 1;
+            return Some(Day::new(date));
+        }
+    }
+}
+
+const WEEKDAYS: [(&str, Weekday); 7] = [
+    ("MO", Weekday::Mon),
+    ("TU", Weekday::Tue),
+    ("WE", Weekday::Wed),
+    ("TH", Weekday::Thu),
+    ("FR", Weekday::Fri),
+    ("SA", Weekday::Sat),
+    ("SU", Weekday::Sun),
+];
+
+// `BYSETPOS` is only supported to select business days
+const BUSINESS_DAYS: &str = "MO,TU,WE,TH,FR";
+
+fn weekday_code(weekday: Weekday) -> &'static str {
+    WEEKDAYS[weekday.num_days_from_monday() as usize].0
+}
+
+fn parse_weekday(code: &str) -> Option<Weekday> {
+    WEEKDAYS
+        .iter()
+        .find(|(c, _)| *c == code)
+        .map(|(_, weekday)| *weekday)
+}
+
+impl fmt::Display for Recurrence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let frequency = match self.frequency {
+            Frequency::Daily => "DAILY",
+            Frequency::Weekly => "WEEKLY",
+            Frequency::Monthly => "MONTHLY",
+            Frequency::Yearly => "YEARLY",
+        };
+        write!(f, "FREQ={frequency}")?;
+        if self.interval.get() != 1 {
+            write!(f, ";INTERVAL={}", self.interval)?;
+        }
+        match &self.day {
+            RecurrenceDay::SameAsStart => {}
+            RecurrenceDay::Weekdays(days) => {
+                f.write_str(";BYDAY=")?;
+                for (i, day) in days.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(",")?;
+                    }
+                    f.write_str(weekday_code(*day))?;
+                }
+            }
+            RecurrenceDay::MonthDay(n) => write!(f, ";BYMONTHDAY={n}")?,
+            RecurrenceDay::NthWeekday(n, weekday) => {
+                write!(f, ";BYDAY={n}{}", weekday_code(*weekday))?
+            }
+            RecurrenceDay::NthBusinessDay(n) => write!(f, ";BYDAY={BUSINESS_DAYS};BYSETPOS={n}")?,
+        }
+        match self.end {
+            RecurrenceEnd::Never => Ok(()),
+            RecurrenceEnd::Count(count) => write!(f, ";COUNT={count}"),
+            RecurrenceEnd::Until(until) => write!(
+                f,
+                ";UNTIL={}{:02}{:02}",
+                until.year(),
+                until.month(),
+                until.day()
+            ),
+        }
+    }
+}
+
+impl str::FromStr for Recurrence {
+    type Err = crate::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || crate::Error::ParseCustom {
+            ty_name: "Recurrence",
+            input: crate::error_text(s),
+        };
+        let rule = s.trim();
+        let rule = rule.strip_prefix("RRULE:").unwrap_or(rule);
+
+        let mut frequency = None;
+        let mut interval = NonZeroU32::MIN;
+        let (mut by_day, mut by_month_day, mut by_set_pos) = (None, None, None);
+        let mut end = RecurrenceEnd::Never;
+        for part in rule.split(';') {
+            let (key, value) = part.split_once('=').ok_or_else(err)?;
+            match key {
+                "FREQ" => {
+                    frequency = Some(match value {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        "YEARLY" => Frequency::Yearly,
+                        _ => return Err(err()),
+                    })
+                }
+                "INTERVAL" => interval = value.parse().map_err(|_| err())?,
+                "BYDAY" => by_day = Some(value),
+                "BYMONTHDAY" => by_month_day = Some(value.parse::<i8>().map_err(|_| err())?),
+                "BYSETPOS" => by_set_pos = Some(value.parse::<i8>().map_err(|_| err())?),
+                "COUNT" => end = RecurrenceEnd::Count(value.parse().map_err(|_| err())?),
+                "UNTIL" => {
+                    // only the date is used of a datetime such as `20241231T235959Z`
+                    let date = value.get(..8).ok_or_else(err)?;
+                    end = RecurrenceEnd::Until(
+                        NaiveDate::parse_from_str(date, "%Y%m%d").map_err(|_| err())?,
+                    )
+                }
+                _ => return Err(err()),
+            }
+        }
+
+        let day = match (by_day, by_month_day, by_set_pos) {
+            (None, None, None) => RecurrenceDay::SameAsStart,
+            (None, Some(n), None) => RecurrenceDay::MonthDay(n),
+            (Some(BUSINESS_DAYS), None, Some(n)) => RecurrenceDay::NthBusinessDay(n),
+            (Some(days), None, None) if days.contains(',') || days.len() == 2 => {
+                RecurrenceDay::Weekdays(
+                    days.split(',')
+                        .map(parse_weekday)
+                        .collect::<Option<_>>()
+                        .ok_or_else(err)?,
+                )
+            }
+            (Some(day), None, None) => {
+                let (n, weekday) = day.split_at(day.len().checked_sub(2).ok_or_else(err)?);
+                RecurrenceDay::NthWeekday(
+                    n.strip_prefix('+')
+                        .unwrap_or(n)
+                        .parse()
+                        .map_err(|_| err())?,
+                    parse_weekday(weekday).ok_or_else(err)?,
+                )
+            }
+            _ => return Err(err()),
+        };
+
+        Recurrence {
+            end,
+            ..Recurrence::new(frequency.ok_or_else(err)?).every(interval)
+        }
+        .on(day)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::date;
+    use crate::{Calendar, DateResolution, Month};
+    use alloc::string::ToString;
+
+    fn days<C: BusinessCalendar>(
+        recurrence: &Recurrence,
+        start: NaiveDate,
+        calendar: C,
+    ) -> Vec<NaiveDate> {
+        recurrence
+            .days(start, calendar)
+            .map(|d| d.start())
+            .collect()
+    }
+
+    #[test]
+    fn test_days() {
+        let calendar = Calendar::saturday_sunday([date(2024, 5, 31)]);
+
+        // the last business day of each month, skipping the holiday on the 31st of May
+        let recurrence = Recurrence::new(Frequency::Monthly)
+            .on(RecurrenceDay::NthBusinessDay(-1))
+            .unwrap()
+            .count(NonZeroU32::new(3).unwrap());
+        assert_eq!(
+            days(&recurrence, date(2024, 4, 1), &calendar),
+            [date(2024, 4, 30), date(2024, 5, 30), date(2024, 6, 28)]
+        );
+
+        // the second Tuesday of every second month
+        let recurrence = Recurrence::new(Frequency::Monthly)
+            .every(NonZeroU32::new(2).unwrap())
+            .on(RecurrenceDay::NthWeekday(2, Weekday::Tue))
+            .unwrap()
+            .until(date(2024, 7, 31));
+        assert_eq!(
+            days(&recurrence, date(2024, 1, 10), &calendar),
+            [date(2024, 3, 12), date(2024, 5, 14), date(2024, 7, 9)]
+        );
+
+        // months without the start day are skipped
+        let recurrence = Recurrence::new(Frequency::Monthly).count(NonZeroU32::new(3).unwrap());
+        assert_eq!(
+            days(&recurrence, date(2024, 1, 31), &calendar),
+            [date(2024, 1, 31), date(2024, 3, 31), date(2024, 5, 31)]
+        );
+
+        let recurrence = Recurrence::new(Frequency::Weekly)
+            .on(RecurrenceDay::Weekdays(Vec::from([
+                Weekday::Mon,
+                Weekday::Thu,
+            ])))
+            .unwrap()
+            .until(date(2024, 5, 16));
+        assert_eq!(
+            days(&recurrence, date(2024, 5, 7), &calendar),
+            [date(2024, 5, 9), date(2024, 5, 13), date(2024, 5, 16)]
+        );
+
+        let months = Recurrence::new(Frequency::Weekly)
+            .periods::<Month, _>(date(2024, 1, 1), &calendar)
+            .take(3)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            months,
+            [
+                Month::from_parts(2024, chrono::Month::January).unwrap(),
+                Month::from_parts(2024, chrono::Month::February).unwrap(),
+                Month::from_parts(2024, chrono::Month::March).unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_never_occurs() {
+        let calendar = Calendar::saturday_sunday([]);
+
+        // every 12 months from February is always February, which never has a 30th
+        let recurrence = Recurrence::new(Frequency::Monthly)
+            .every(NonZeroU32::new(12).unwrap())
+            .on(RecurrenceDay::MonthDay(30))
+            .unwrap();
+        assert!(days(&recurrence, date(2024, 2, 1), &calendar).is_empty());
+        let recurrence = recurrence.count(NonZeroU32::new(3).unwrap());
+        assert!(days(&recurrence, date(2024, 2, 1), &calendar).is_empty());
+        assert_eq!(
+            days(&recurrence, date(2024, 1, 1), &calendar),
+            [date(2024, 1, 30), date(2025, 1, 30), date(2026, 1, 30)]
+        );
+
+        // rare occurrences are still found
+        let recurrence = Recurrence::new(Frequency::Monthly)
+            .every(NonZeroU32::new(12).unwrap())
+            .on(RecurrenceDay::NthWeekday(5, Weekday::Thu))
+            .unwrap()
+            .count(NonZeroU32::new(2).unwrap());
+        assert_eq!(
+            days(&recurrence, date(2024, 2, 1), &calendar),
+            [date(2024, 2, 29), date(2052, 2, 29)]
+        );
+
+        assert!(Recurrence::new(Frequency::Weekly)
+            .on(RecurrenceDay::NthBusinessDay(8))
+            .is_err());
+        assert!(Recurrence::new(Frequency::Monthly)
+            .on(RecurrenceDay::NthBusinessDay(-32))
+            .is_err());
+    }
+
+    #[test]
+    fn test_rrule() {
+        for rule in [
+            "FREQ=DAILY",
+            "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH;COUNT=10",
+            "FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1",
+            "FREQ=MONTHLY;BYMONTHDAY=-1;UNTIL=20241231",
+            "FREQ=YEARLY;BYDAY=-1FR",
+        ] {
+            assert_eq!(rule.parse::<Recurrence>().unwrap().to_string(), rule);
+        }
+        assert_eq!(
+            "RRULE:FREQ=MONTHLY;BYDAY=+2TU;UNTIL=20241231T235959Z"
+                .parse::<Recurrence>()
+                .unwrap(),
+            Recurrence::new(Frequency::Monthly)
+                .on(RecurrenceDay::NthWeekday(2, Weekday::Tue))
+                .unwrap()
+                .until(date(2024, 12, 31))
+        );
+
+        assert!("FREQ=HOURLY".parse::<Recurrence>().is_err());
+        assert!("FREQ=MONTHLY;BYHOUR=9".parse::<Recurrence>().is_err());
+        assert!("FREQ=MONTHLY;BYDAY=SA,SU;BYSETPOS=1"
+            .parse::<Recurrence>()
+            .is_err());
+        assert!(matches!(
+            "FREQ=WEEKLY;BYMONTHDAY=1".parse::<Recurrence>(),
+            Err(crate::Error::InvalidRecurrence { .. })
+        ));
+        assert!(Recurrence::new(Frequency::Monthly)
+            .on(RecurrenceDay::MonthDay(32))
+            .is_err());
+    }
+}
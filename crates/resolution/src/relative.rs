@@ -0,0 +1,397 @@
+use crate::{DynPeriod, DynResolution, TimeRange, TimeResolution};
+use arrayvec::ArrayVec;
+use chrono::{DateTime, Duration, Months, Offset, TimeZone, Utc};
+use core::num::NonZeroU32;
+use core::{fmt, str};
+
+/// `RelativePeriod` describes a range of time relative to a reference time, usually now, such
+/// as `last 3 complete months` or `month-to-date`.
+///
+/// The expression has its own resolution (the month in `month-to-date`), which needn't be the
+/// same as the resolution of the `TimeRange` it is evaluated into, so `month-to-date` can be
+/// evaluated as a range of `Day`s or `Hour`s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RelativePeriod {
+    /// The period containing the reference time, eg `this month`
+    Current(DynResolution),
+    /// The `n` complete periods before the one containing the reference time, eg `last 3 complete months`
+    Previous(DynResolution, NonZeroU32),
+    /// The `n` periods up to and including the one containing the reference time, eg `last 7 days`
+    Rolling(DynResolution, NonZeroU32),
+    /// From the start of the period containing the reference time up to the reference time, eg `month-to-date`
+    ToDate(DynResolution),
+    /// The period containing the reference time, one year earlier, eg `same month last year`
+    SamePeriodLastYear(DynResolution),
+}
+
+// the names which can be used for resolutions in expressions, other resolutions use their
+// `Display` format instead
+const UNITS: &[(&str, DynResolution)] = &[
+    ("millisecond", DynResolution::Millis(NonZeroU32::MIN)),
+    ("second", DynResolution::Seconds(NonZeroU32::MIN)),
+    ("minute", DynResolution::Minutes(NonZeroU32::MIN)),
+    ("hour", DynResolution::Minutes(NonZeroU32::new(60).unwrap())),
+    ("day", DynResolution::Day),
+    ("week", DynResolution::IsoWeek),
+    ("month", DynResolution::Month),
+    ("quarter", DynResolution::Quarter),
+    ("half-year", DynResolution::HalfYear),
+    ("year", DynResolution::Year),
+];
+
+// abbreviations for the common `ToDate` expressions
+const TO_DATE: &[(&str, DynResolution)] = &[
+    ("wtd", DynResolution::IsoWeek),
+    ("mtd", DynResolution::Month),
+    ("qtd", DynResolution::Quarter),
+    ("ytd", DynResolution::Year),
+];
+
+impl RelativePeriod {
+    pub fn resolution(&self) -> DynResolution {
+        match self {
+            RelativePeriod::Current(r)
+            | RelativePeriod::Previous(r, _)
+            | RelativePeriod::Rolling(r, _)
+            | RelativePeriod::ToDate(r)
+            | RelativePeriod::SamePeriodLastYear(r) => *r,
+        }
+    }
+
+    /// The start (inclusive) and end (exclusive) of the expression relative to `now`
+    pub fn bounds(&self, now: DateTime<Utc>) -> (DateTime<Utc>, DateTime<Utc>) {
+        let current = self.resolution().period(now);
+        match self {
+            RelativePeriod::Current(_) => {
+                (current.start_datetime(), current.succ().start_datetime())
+            }
+            RelativePeriod::Previous(_, n) => (
+                current.pred_n(u64::from(n.get())).start_datetime(),
+                current.start_datetime(),
+            ),
+            RelativePeriod::Rolling(_, n) => (
+                current.pred_n(u64::from(n.get() - 1)).start_datetime(),
+                current.succ().start_datetime(),
+            ),
+            RelativePeriod::ToDate(_) => (current.start_datetime(), now),
+            RelativePeriod::SamePeriodLastYear(r) => {
+                let earlier: DynPeriod = r.period(
+                    now.checked_sub_months(Months::new(12))
+                        .expect("valid datetime"),
+                );
+                (earlier.start_datetime(), earlier.succ().start_datetime())
+            }
+        }
+    }
+
+    /// The periods of `P` which overlap the expression relative to `now`, where `containing`
+    /// finds the period of `P` containing a datetime, eg `Day::containing`.
+    ///
+    /// The expression is evaluated against the local time of `now`, so `month-to-date` starts at
+    /// local midnight on the first of the month in the zone of `now`. Any resolution can be used,
+    /// including local resolutions such as `|now| Day::from(now.date_naive())`, or `Zoned`
+    /// resolutions.
+    ///
+    /// For `ToDate`, the period containing `now` is included, unless `now` is exactly the start
+    /// of the expression's period.
+    pub fn evaluate<P, Z>(
+        &self,
+        now: DateTime<Z>,
+        containing: impl Fn(DateTime<Z>) -> P,
+    ) -> TimeRange<P>
+    where
+        P: TimeResolution,
+        Z: TimeZone,
+    {
+        let (start, end) = self.bounds(now.naive_local().and_utc());
+        let last = (end - Duration::milliseconds(1)).max(start);
+        let in_zone = |local: DateTime<Utc>| {
+            let (zone, local) = (now.timezone(), local.naive_utc());
+            zone.from_local_datetime(&local)
+                .earliest()
+                .unwrap_or_else(|| {
+                    // a local time skipped by a daylight savings transition is found with the offset
+                    // before the transition, which lands an hour or so earlier
+                    let offset = zone.offset_from_utc_datetime(&local).fix();
+                    zone.from_utc_datetime(&(local - offset))
+                })
+        };
+        TimeRange::from_bounds(containing(in_zone(start)), containing(in_zone(last)))
+    }
+}
+
+fn unit_name(resolution: DynResolution) -> Option<&'static str> {
+    UNITS
+        .iter()
+        .find(|(_, r)| *r == resolution)
+        .map(|(name, _)| *name)
+}
+
+fn write_unit(f: &mut fmt::Formatter<'_>, resolution: DynResolution, plural: bool) -> fmt::Result {
+    match unit_name(resolution) {
+        Some(name) if plural => write!(f, "{name}s"),
+        Some(name) => f.write_str(name),
+        None => write!(f, "{resolution}"),
+    }
+}
+
+fn parse_unit(input: &str, plural: bool) -> Option<DynResolution> {
+    let name = if plural {
+        input
+            .strip_suffix('s')
+            .or_else(|| input.strip_suffix('S'))
+            .unwrap_or(input)
+    } else {
+        input
+    };
+    UNITS
+        .iter()
+        .find(|(n, _)| n.eq_ignore_ascii_case(name))
+        .map(|(_, r)| *r)
+        .or_else(|| input.parse().ok())
+}
+
+impl fmt::Display for RelativePeriod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RelativePeriod::Current(r) => {
+                f.write_str("this ")?;
+                write_unit(f, *r, false)
+            }
+            RelativePeriod::Previous(r, n) if n.get() == 1 => {
+                f.write_str("last complete ")?;
+                write_unit(f, *r, false)
+            }
+            RelativePeriod::Previous(r, n) => {
+                write!(f, "last {n} complete ")?;
+                write_unit(f, *r, true)
+            }
+            RelativePeriod::Rolling(r, n) => {
+                write!(f, "last {n} ")?;
+                write_unit(f, *r, n.get() != 1)
+            }
+            RelativePeriod::ToDate(r) => {
+                write_unit(f, *r, false)?;
+                f.write_str("-to-date")
+            }
+            RelativePeriod::SamePeriodLastYear(r) => {
+                f.write_str("same ")?;
+                write_unit(f, *r, false)?;
+                f.write_str(" last year")
+            }
+        }
+    }
+}
+
+impl str::FromStr for RelativePeriod {
+    type Err = crate::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // the longest expressions, such as `last 3 complete months`, have four words
+        let mut tokens = ArrayVec::<&str, 4>::new();
+        let fits = s
+            .split_whitespace()
+            .all(|token| tokens.try_push(token).is_ok());
+        fits.then(|| parse_expression(&tokens))
+            .flatten()
+            .ok_or_else(|| crate::Error::ParseCustom {
+                ty_name: "RelativePeriod",
+                input: crate::error_text(s),
+            })
+    }
+}
+
+fn parse_expression(tokens: &[&str]) -> Option<RelativePeriod> {
+    let is = |token: &str, keyword: &str| token.eq_ignore_ascii_case(keyword);
+    let count = |token: &str| token.parse::<NonZeroU32>().ok();
+
+    match tokens {
+        [this, unit] if is(this, "this") || is(this, "current") => {
+            parse_unit(unit, false).map(RelativePeriod::Current)
+        }
+        [previous, unit] if is(previous, "previous") => {
+            parse_unit(unit, false).map(|r| RelativePeriod::Previous(r, NonZeroU32::MIN))
+        }
+        [last, complete, unit] if is(last, "last") && is(complete, "complete") => {
+            parse_unit(unit, false).map(|r| RelativePeriod::Previous(r, NonZeroU32::MIN))
+        }
+        [previous, n, units] if is(previous, "previous") => Some(RelativePeriod::Previous(
+            parse_unit(units, true)?,
+            count(n)?,
+        )),
+        [last, n, complete, units] if is(last, "last") && is(complete, "complete") => Some(
+            RelativePeriod::Previous(parse_unit(units, true)?, count(n)?),
+        ),
+        [last, n, units] if is(last, "last") => {
+            Some(RelativePeriod::Rolling(parse_unit(units, true)?, count(n)?))
+        }
+        [unit, to, date] if is(to, "to") && is(date, "date") => {
+            parse_unit(unit, false).map(RelativePeriod::ToDate)
+        }
+        [same, unit, last, year] if is(same, "same") && is(last, "last") && is(year, "year") => {
+            parse_unit(unit, false).map(RelativePeriod::SamePeriodLastYear)
+        }
+        [token] => TO_DATE
+            .iter()
+            .find(|(abbreviation, _)| is(token, abbreviation))
+            .map(|(_, r)| RelativePeriod::ToDate(*r))
+            .or_else(|| {
+                let split = token.len().checked_sub("-to-date".len())?;
+                let (unit, suffix) = (token.get(..split)?, token.get(split..)?);
+                is(suffix, "-to-date")
+                    .then(|| parse_unit(unit, false))
+                    .flatten()
+                    .map(RelativePeriod::ToDate)
+            }),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{date, Sydney};
+    use crate::{Day, Hour, Month, Quarter, TypedResolution, Year, Zoned};
+    use alloc::string::ToString;
+    use chrono::{FixedOffset, NaiveDate};
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 5, 15, 10, 30, 0).unwrap()
+    }
+
+    fn days(start: (i32, u32, u32), end: (i32, u32, u32)) -> TimeRange<Day> {
+        let day = |(y, m, d)| Day::new(NaiveDate::from_ymd_opt(y, m, d).unwrap());
+        TimeRange::from_bounds(day(start), day(end))
+    }
+
+    fn parse(s: &str) -> RelativePeriod {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_evaluate() {
+        assert_eq!(
+            parse("last 3 complete months").evaluate(now(), Day::containing),
+            days((2024, 2, 1), (2024, 4, 30))
+        );
+        assert_eq!(
+            parse("last 3 complete months").evaluate(now(), Month::containing),
+            TimeRange::from_bounds(
+                Month::from_parts(2024, chrono::Month::February).unwrap(),
+                Month::from_parts(2024, chrono::Month::April).unwrap(),
+            )
+        );
+        assert_eq!(
+            parse("month-to-date").evaluate(now(), Day::containing),
+            days((2024, 5, 1), (2024, 5, 15))
+        );
+        assert_eq!(
+            parse("mtd").evaluate(now(), Hour::containing).len().get(),
+            14 * 24 + 11
+        );
+        assert_eq!(
+            parse("last 7 days").evaluate(now(), Day::containing),
+            days((2024, 5, 9), (2024, 5, 15))
+        );
+        assert_eq!(
+            parse("this quarter")
+                .evaluate(now(), Month::containing)
+                .len()
+                .get(),
+            3
+        );
+        assert_eq!(
+            parse("same quarter last year").evaluate(now(), Quarter::containing),
+            TimeRange::from_bounds(
+                Quarter::from_parts(2023, crate::QuarterNumber::Q2),
+                Quarter::from_parts(2023, crate::QuarterNumber::Q2),
+            )
+        );
+        // a coarser resolution covers the expression
+        assert_eq!(
+            parse("last 7 days").evaluate(now(), Year::containing),
+            TimeRange::from_bounds(Year::new(2024), Year::new(2024))
+        );
+        // at the start of the year, year-to-date is only the first day
+        let new_year = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(
+            parse("ytd").evaluate(new_year, Day::containing),
+            days((2024, 1, 1), (2024, 1, 1))
+        );
+    }
+
+    #[test]
+    fn test_zoned() {
+        // already the 16th in the zone
+        let zone = FixedOffset::east_opt(14 * 3600).unwrap();
+        let local = parse("month-to-date")
+            .evaluate(now().with_timezone(&zone), |now| Day::new(now.date_naive()));
+        assert_eq!(local, days((2024, 5, 1), (2024, 5, 16)));
+
+        // still the 14th in the zone
+        let zone = FixedOffset::west_opt(11 * 3600).unwrap();
+        let local = parse("month-to-date")
+            .evaluate(now().with_timezone(&zone), |now| Day::new(now.date_naive()));
+        assert_eq!(local, days((2024, 5, 1), (2024, 5, 14)));
+
+        let zoned = parse("month-to-date").evaluate(now().with_timezone(&Sydney), |now| {
+            Zoned::<Day, _>::from_date(now.date_naive(), Sydney)
+        });
+        assert_eq!(zoned.local(), days((2024, 5, 1), (2024, 5, 15)));
+
+        // daylight savings ends at 3am on the 7th of April 2024, so that day has 25 hours
+        let now = Utc.with_ymd_and_hms(2024, 4, 7, 13, 0, 0).unwrap();
+        let hours = parse("this day").evaluate(now.with_timezone(&Sydney), Zoned::<Hour, _>::from);
+        assert_eq!(hours.len().get(), 25);
+        assert_eq!(
+            hours.start().local_resolution().start_datetime(),
+            date(2024, 4, 7).and_hms_opt(0, 0, 0).unwrap().and_utc()
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        for (input, expected) in [
+            ("this month", RelativePeriod::Current(DynResolution::Month)),
+            (
+                "Previous Quarter",
+                RelativePeriod::Previous(DynResolution::Quarter, NonZeroU32::MIN),
+            ),
+            (
+                "last 12 hours",
+                RelativePeriod::Rolling(
+                    DynResolution::Minutes(NonZeroU32::new(60).unwrap()),
+                    NonZeroU32::new(12).unwrap(),
+                ),
+            ),
+            ("year to date", RelativePeriod::ToDate(DynResolution::Year)),
+            ("YTD", RelativePeriod::ToDate(DynResolution::Year)),
+            (
+                "last 2 complete Minutes[Length:5]",
+                RelativePeriod::Previous(
+                    DynResolution::Minutes(NonZeroU32::new(5).unwrap()),
+                    NonZeroU32::new(2).unwrap(),
+                ),
+            ),
+        ] {
+            assert_eq!(parse(input), expected, "{input}");
+        }
+
+        for display in [
+            "this month",
+            "last complete quarter",
+            "last 3 complete months",
+            "last 1 day",
+            "last 7 days",
+            "half-year-to-date",
+            "same week last year",
+            "this Minutes[Length:5]",
+        ] {
+            assert_eq!(parse(display).to_string(), display);
+        }
+
+        assert!("last 0 days".parse::<RelativePeriod>().is_err());
+        assert!("last three days".parse::<RelativePeriod>().is_err());
+        assert!("next month".parse::<RelativePeriod>().is_err());
+        assert!("fortnight-to-date".parse::<RelativePeriod>().is_err());
+    }
+}
@@ -0,0 +1,200 @@
+use crate::{
+    BusinessCalendar, BusinessDay, DateResolution, DateResolutionExt, Day, Month, TimeResolution,
+};
+use chrono::{Datelike, Days, NaiveDate};
+
+/// `RollConvention` decides which business day to use when a date falls on a weekend or holiday
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum RollConvention {
+    /// The date is used even if it is not a business day
+    Unadjusted,
+    /// The first business day on or after the date
+    #[default]
+    Following,
+    /// The first business day on or after the date, unless that is in the next month, in which
+    /// case the last business day before the date
+    ModifiedFollowing,
+    /// The last business day on or before the date
+    Preceding,
+    /// The last business day on or before the date, unless that is in the previous month, in
+    /// which case the first business day after the date
+    ModifiedPreceding,
+    /// The last business day of the month of the date
+    EndOfMonth,
+}
+
+impl RollConvention {
+    pub fn roll<C: BusinessCalendar>(&self, date: NaiveDate, calendar: C) -> NaiveDate {
+        let following = || BusinessDay::following(date, calendar).start();
+        let preceding = || BusinessDay::preceding(date, calendar).start();
+        match self {
+            RollConvention::Unadjusted => date,
+            RollConvention::Following => following(),
+            RollConvention::Preceding => preceding(),
+            RollConvention::ModifiedFollowing => {
+                let rolled = following();
+                if rolled.month() == date.month() {
+                    rolled
+                } else {
+                    preceding()
+                }
+            }
+            RollConvention::ModifiedPreceding => {
+                let rolled = preceding();
+                if rolled.month() == date.month() {
+                    rolled
+                } else {
+                    following()
+                }
+            }
+            RollConvention::EndOfMonth => {
+                let last = Month::from(date).end();
+                BusinessDay::preceding(last, calendar).start()
+            }
+        }
+    }
+}
+
+/// The offset applied to a date by a `DateAdjustment`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DateOffset {
+    /// The nth business day after the date, or before it when negative. When zero, this is
+    /// the date itself.
+    BusinessDays(i64),
+    /// The nth calendar day after the date, or before it when negative
+    CalendarDays(i64),
+}
+
+/// `DateAdjustment` finds a date such as a payment or settlement date from another date, such
+/// as `5 business days after, modified following`.
+///
+/// The offset is applied first and the result is then rolled according to the convention, so
+/// with a non-zero `DateOffset::BusinessDays` the convention only matters for
+/// `RollConvention::EndOfMonth`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DateAdjustment {
+    offset: DateOffset,
+    convention: RollConvention,
+}
+
+impl DateAdjustment {
+    pub fn new(offset: DateOffset, convention: RollConvention) -> DateAdjustment {
+        DateAdjustment { offset, convention }
+    }
+    pub fn offset(&self) -> DateOffset {
+        self.offset
+    }
+    pub fn convention(&self) -> RollConvention {
+        self.convention
+    }
+    pub fn apply<C: BusinessCalendar>(&self, date: NaiveDate, calendar: C) -> NaiveDate {
+        let offset = match self.offset {
+            DateOffset::BusinessDays(0) => date,
+            DateOffset::BusinessDays(n) if n > 0 => BusinessDay::preceding(date, calendar)
+                .succ_n(n.unsigned_abs())
+                .start(),
+            DateOffset::BusinessDays(n) => BusinessDay::following(date, calendar)
+                .pred_n(n.unsigned_abs())
+                .start(),
+            DateOffset::CalendarDays(n) if n >= 0 => date
+                .checked_add_days(Days::new(n.unsigned_abs()))
+                .expect("valid date"),
+            DateOffset::CalendarDays(n) => date
+                .checked_sub_days(Days::new(n.unsigned_abs()))
+                .expect("valid date"),
+        };
+        self.convention.roll(offset, calendar)
+    }
+    /// Apply the adjustment to the last day of the `period`, eg to find the settlement date of
+    /// a delivery period
+    pub fn after_end<P: DateResolution, C: BusinessCalendar>(&self, period: P, calendar: C) -> Day {
+        Day::new(self.apply(period.end(), calendar))
+    }
+    /// Apply the adjustment to the first day of the `period`
+    pub fn after_start<P: DateResolution, C: BusinessCalendar>(
+        &self,
+        period: P,
+        calendar: C,
+    ) -> Day {
+        Day::new(self.apply(period.start(), calendar))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::date;
+    use crate::Calendar;
+
+    #[test]
+    fn test_roll() {
+        // Friday 31 May 2024 is a holiday
+        let cal = Calendar::saturday_sunday([date(2024, 5, 31)]);
+        let sat = date(2024, 6, 1);
+        assert_eq!(RollConvention::Unadjusted.roll(sat, &cal), sat);
+        assert_eq!(RollConvention::Following.roll(sat, &cal), date(2024, 6, 3));
+        assert_eq!(RollConvention::Preceding.roll(sat, &cal), date(2024, 5, 30));
+        assert_eq!(
+            RollConvention::ModifiedFollowing.roll(sat, &cal),
+            date(2024, 6, 3)
+        );
+        assert_eq!(
+            RollConvention::ModifiedPreceding.roll(sat, &cal),
+            date(2024, 6, 3)
+        );
+        assert_eq!(
+            RollConvention::ModifiedFollowing.roll(date(2024, 8, 31), &cal),
+            date(2024, 8, 30)
+        );
+        assert_eq!(
+            RollConvention::EndOfMonth.roll(date(2024, 5, 2), &cal),
+            date(2024, 5, 30)
+        );
+        // business days are not changed, except by end of month
+        let wed = date(2024, 6, 5);
+        for convention in [
+            RollConvention::Following,
+            RollConvention::ModifiedFollowing,
+            RollConvention::Preceding,
+            RollConvention::ModifiedPreceding,
+        ] {
+            assert_eq!(convention.roll(wed, &cal), wed);
+        }
+    }
+
+    #[test]
+    fn test_adjustment() {
+        let cal = Calendar::saturday_sunday([date(2024, 5, 31)]);
+        let may = Month::from_parts(2024, chrono::Month::May).unwrap();
+
+        let settlement = DateAdjustment::new(
+            DateOffset::BusinessDays(5),
+            RollConvention::ModifiedFollowing,
+        );
+        assert_eq!(settlement.after_end(may, &cal), Day::new(date(2024, 6, 7)));
+        assert_eq!(settlement.apply(date(2024, 6, 1), &cal), date(2024, 6, 7));
+        assert_eq!(
+            DateAdjustment::new(DateOffset::BusinessDays(-2), RollConvention::Following)
+                .after_start(may, &cal),
+            Day::new(date(2024, 4, 29))
+        );
+        assert_eq!(
+            DateAdjustment::new(DateOffset::BusinessDays(0), RollConvention::Preceding)
+                .after_end(may, &cal),
+            Day::new(date(2024, 5, 30))
+        );
+        assert_eq!(
+            DateAdjustment::new(
+                DateOffset::CalendarDays(30),
+                RollConvention::ModifiedFollowing
+            )
+            .apply(date(2024, 5, 1), &cal),
+            date(2024, 5, 30)
+        );
+        assert_eq!(
+            DateAdjustment::new(DateOffset::CalendarDays(-1), RollConvention::EndOfMonth)
+                .apply(date(2024, 6, 1), &cal),
+            date(2024, 5, 30)
+        );
+    }
+}
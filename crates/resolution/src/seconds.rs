@@ -0,0 +1,249 @@
+use crate::{Error, FromMonotonic, Monotonic, SubDateResolution, TimeResolution};
+#[cfg(feature = "serde")]
+use alloc::{format, string::String};
+use chrono::{DateTime, Datelike, Duration, NaiveDateTime, NaiveTime, Timelike, Utc};
+use core::{fmt, str};
+
+pub(crate) const FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// Note that for sensible behaviour, the N chosen should be a number that divides into a minute
+/// with no remainder (1, 2, 3, 4, 5, 6, 10, 12, 15, 20, 30).
+///
+/// For periods of a minute or longer, use `Minutes`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "Seconds_", into = "Seconds_"))]
+pub struct Seconds<const N: u32> {
+    index: i64,
+}
+
+#[cfg(feature = "serde")]
+impl<const N: u32> TryFrom<Seconds_> for Seconds<N> {
+    type Error = String;
+    fn try_from(value: Seconds_) -> Result<Self, Self::Error> {
+        if value.length == N {
+            Ok(Seconds { index: value.index })
+        } else {
+            Err(format!(
+                "To create a Seconds[Length:{}], the length field should be {} but was instead {}",
+                N, N, value.length
+            ))
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<const N: u32> From<Seconds<N>> for Seconds_ {
+    fn from(s: Seconds<N>) -> Self {
+        Seconds_ {
+            index: s.index,
+            length: N,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize, serde::Serialize)]
+pub(crate) struct Seconds_ {
+    index: i64,
+    length: u32,
+}
+
+impl<const N: u32> From<DateTime<Utc>> for Seconds<N> {
+    fn from(d: DateTime<Utc>) -> Self {
+        Seconds {
+            index: d.timestamp().div_euclid(i64::from(N)),
+        }
+    }
+}
+
+pub(crate) fn format_datetime(n: DateTime<Utc>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+        f,
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+        n.year(),
+        n.month(),
+        n.day(),
+        n.hour(),
+        n.minute(),
+        n.second()
+    )
+}
+
+pub(crate) fn parse_datetime(input: &str, format: &'static str) -> Result<DateTime<Utc>, Error> {
+    NaiveDateTime::parse_from_str(input, format)
+        .map(|dt| dt.and_utc())
+        .map_err(|e| Error::ParseDateInternal {
+            message: crate::error_text(e),
+            input: crate::error_text(input),
+            format,
+        })
+}
+
+impl<const N: u32> str::FromStr for Seconds<N> {
+    type Err = crate::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if N == 1 {
+            return Ok(parse_datetime(s, FORMAT)?.into());
+        }
+        let err = |input| crate::Error::ParseCustom {
+            ty_name: "Seconds",
+            input,
+        };
+        let (start, end) = s
+            .split_once(" => ")
+            .ok_or_else(|| err(crate::error_text(s)))?;
+
+        let start = parse_datetime(start, FORMAT)?;
+        if start.num_seconds_from_midnight().rem_euclid(N) != 0 {
+            return Err(err(crate::error_text(format_args!(
+                "Invalid start for Seconds[Length:{}]: {}",
+                N, start
+            ))));
+        }
+        let end = parse_datetime(end, FORMAT)?;
+        if start + Duration::seconds(i64::from(N)) != end {
+            return Err(err(crate::error_text(format_args!(
+                "Invalid start-end combination for Seconds[Length:{}]: {}",
+                N, s
+            ))));
+        }
+
+        Ok(start.into())
+    }
+}
+
+impl<const N: u32> fmt::Display for Seconds<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if N == 1 {
+            format_datetime(self.start_datetime(), f)
+        } else {
+            format_datetime(self.start_datetime(), f)?;
+            f.write_str(" => ")?;
+            format_datetime(self.succ().start_datetime(), f)
+        }
+    }
+}
+
+impl<const N: u32> crate::TimeResolution for Seconds<N> {
+    fn succ_n(&self, n: u64) -> Seconds<N> {
+        Seconds {
+            index: self.index + i64::try_from(n).unwrap(),
+        }
+    }
+    fn pred_n(&self, n: u64) -> Seconds<N> {
+        Seconds {
+            index: self.index - i64::try_from(n).unwrap(),
+        }
+    }
+    fn start_datetime(&self) -> DateTime<Utc> {
+        DateTime::<Utc>::from_timestamp(self.index * i64::from(N), 0).expect("valid timestamp")
+    }
+    fn fmt_name(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Seconds[Length:{}]", N)
+    }
+}
+
+impl<const N: u32> Monotonic for Seconds<N> {
+    fn to_monotonic(&self) -> i64 {
+        self.index
+    }
+    fn between(&self, other: Self) -> i64 {
+        other.index - self.index
+    }
+}
+
+impl<const N: u32> FromMonotonic for Seconds<N> {
+    fn from_monotonic(index: i64) -> Self {
+        Seconds { index }
+    }
+}
+
+impl<const N: u32> SubDateResolution for Seconds<N> {
+    fn occurs_on_date(&self) -> chrono::NaiveDate {
+        self.start_datetime().date_naive()
+    }
+    fn first_on_day(day: chrono::NaiveDate, _params: Self::Params) -> Self {
+        day.and_time(NaiveTime::MIN).and_utc().into()
+    }
+
+    type Params = ();
+
+    fn params(&self) -> Self::Params {}
+
+    fn from_utc_datetime(datetime: DateTime<Utc>, _params: Self::Params) -> Self {
+        datetime.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DateResolution, DateResolutionExt, Day, FiveMinute, Minute, TimeRange};
+    use alloc::string::ToString;
+
+    fn datetime(h: u32, m: u32, s: u32) -> DateTime<Utc> {
+        chrono::NaiveDate::from_ymd_opt(2021, 1, 1)
+            .unwrap()
+            .and_hms_opt(h, m, s)
+            .unwrap()
+            .and_utc()
+    }
+
+    #[test]
+    fn test_parse() {
+        let s = "2021-01-01 10:05:03".parse::<Seconds<1>>().unwrap();
+        assert_eq!(s, datetime(10, 5, 3).into());
+        assert_eq!(s.succ().to_string(), "2021-01-01 10:05:04");
+
+        let s = "2021-01-01 10:05:04 => 2021-01-01 10:05:08"
+            .parse::<Seconds<4>>()
+            .unwrap();
+        assert_eq!(s.start_datetime(), datetime(10, 5, 4));
+        assert_eq!(s.to_string(), "2021-01-01 10:05:04 => 2021-01-01 10:05:08");
+        assert_eq!(Seconds::<4>::from(datetime(10, 5, 7)), s);
+
+        assert!("2021-01-01 10:05:03 => 2021-01-01 10:05:07"
+            .parse::<Seconds<4>>()
+            .is_err());
+        assert!("2021-01-01 10:05:04 => 2021-01-01 10:05:09"
+            .parse::<Seconds<4>>()
+            .is_err());
+        assert!("2021-01-01 10:05".parse::<Seconds<1>>().is_err());
+    }
+
+    #[test]
+    fn test_rescale() {
+        let min = Minute::from(datetime(10, 5, 0));
+        let secs = TimeRange::from_bounds(min, min).rescale::<Seconds<4>>();
+        assert_eq!(secs.len().get(), 15);
+        assert_eq!(secs.start().start_datetime(), datetime(10, 5, 0));
+        assert_eq!(secs.end().succ().start_datetime(), datetime(10, 6, 0));
+
+        let five = FiveMinute::from(datetime(10, 5, 0));
+        assert_eq!(
+            TimeRange::from_bounds(five, five)
+                .rescale::<Seconds<1>>()
+                .len()
+                .get(),
+            300
+        );
+
+        let day = Day::new(chrono::NaiveDate::from_ymd_opt(2021, 1, 1).unwrap());
+        let secs = day.to_sub_date_resolution::<Seconds<1>>();
+        assert_eq!(secs.len().get(), 86400);
+        assert_eq!(secs.start().occurs_on_date(), day.start());
+        assert_eq!(secs.end().occurs_on_date(), day.start());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_roundtrip() {
+        let s = Seconds::<4>::from(datetime(10, 5, 7));
+        assert_eq!(
+            s,
+            serde_json::from_str(&serde_json::to_string(&s).unwrap()).unwrap()
+        );
+        assert!(serde_json::from_str::<Seconds<5>>(&serde_json::to_string(&s).unwrap()).is_err());
+    }
+}
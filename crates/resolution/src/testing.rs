@@ -0,0 +1,321 @@
+//! Conformance checks for implementations of `TimeResolution`, including those outside this
+//! crate.
+//!
+//! Each check panics with a description of the first failure, so they are intended to be run
+//! from a `#[test]`, usually over the periods containing each of the `sample_datetimes`:
+//!
+//! ```
+//! use resolution::{testing, Day, Month, TypedResolution};
+//!
+//! testing::check_periods(testing::sample_periods(Month::containing));
+//! testing::check_dates(testing::sample_periods(Month::containing));
+//! testing::check_display_parse(testing::sample_periods(Month::containing));
+//! testing::check_rescale::<Month, Day>(testing::sample_periods(Month::containing));
+//! ```
+//!
+//! `check_serde` is available when the `serde_json` feature is enabled along with `serde`.
+
+use crate::{DateResolution, FromMonotonic, Rescalable, TimeRange, TimeResolution};
+use alloc::string::ToString;
+use chrono::{DateTime, NaiveDate, TimeDelta, Utc};
+use core::{fmt, iter, str};
+
+// the number of periods either side of each period which are stepped through
+const STEPS: u64 = 3;
+
+// roughly 38,000 datetimes between the years 1 and 9999, at intervals which aren't a whole
+// number of days, hours or minutes, so that they fall at many different times of day
+fn sparse_datetimes() -> impl Iterator<Item = DateTime<Utc>> {
+    let first = NaiveDate::from_ymd_opt(1, 1, 1)
+        .expect("valid date")
+        .and_hms_opt(0, 0, 0)
+        .expect("valid time")
+        .and_utc();
+    let last = NaiveDate::from_ymd_opt(9999, 12, 31)
+        .expect("valid date")
+        .and_hms_opt(23, 59, 59)
+        .expect("valid time")
+        .and_utc();
+    let step = TimeDelta::days(97)
+        + TimeDelta::hours(3)
+        + TimeDelta::minutes(7)
+        + TimeDelta::seconds(11)
+        + TimeDelta::milliseconds(13);
+    iter::successors(Some(first), move |dt| Some(*dt + step)).take_while(move |dt| *dt <= last)
+}
+
+// each day around the leap years 2023 to 2025, at a time which moves through the day
+fn dense_datetimes() -> impl Iterator<Item = DateTime<Utc>> {
+    let first = NaiveDate::from_ymd_opt(2023, 1, 1)
+        .expect("valid date")
+        .and_hms_opt(0, 0, 0)
+        .expect("valid time")
+        .and_utc();
+    let step = TimeDelta::days(1) + TimeDelta::minutes(7) + TimeDelta::milliseconds(1);
+    iter::successors(Some(first), move |dt| Some(*dt + step)).take(3 * 366)
+}
+
+/// Datetimes spread over the years 1 to 9999, along with each day from 2023 to 2025, at many
+/// different times of day.
+pub fn sample_datetimes() -> impl Iterator<Item = DateTime<Utc>> {
+    sparse_datetimes().chain(dense_datetimes())
+}
+
+/// The period found by `containing` for each of the `sample_datetimes`, after checking that it
+/// does contain the datetime.
+pub fn sample_periods<P>(containing: impl Fn(DateTime<Utc>) -> P) -> impl Iterator<Item = P>
+where
+    P: TimeResolution + fmt::Debug,
+{
+    sample_datetimes().map(move |datetime| {
+        let period = containing(datetime);
+        assert!(
+            period.start_datetime() <= datetime && datetime < period.succ().start_datetime(),
+            "{period:?} doesn't contain {datetime}"
+        );
+        period
+    })
+}
+
+/// Check the monotonic index, stepping and ordering of a period, and of the periods around it:
+///
+/// - `from_monotonic` is the inverse of `to_monotonic`, and `between` is their difference
+/// - `succ` and `pred` are inverses, and `succ_n` and `pred_n` agree with repeating them
+/// - each period starts later than the one before it, and compares as greater than it
+pub fn check_period<P>(period: P)
+where
+    P: TimeResolution + FromMonotonic + fmt::Debug,
+{
+    let index = period.to_monotonic();
+    assert_eq!(
+        P::from_monotonic(index),
+        period,
+        "from_monotonic({index}) isn't {period:?}"
+    );
+
+    let mut succ = period;
+    let mut pred = period;
+    for n in 1..=STEPS {
+        let (prev_succ, prev_pred) = (succ, pred);
+        succ = succ.succ();
+        pred = pred.pred();
+
+        assert_eq!(succ.pred(), prev_succ, "pred of {succ:?}");
+        assert_eq!(pred.succ(), prev_pred, "succ of {pred:?}");
+        assert_eq!(period.succ_n(n), succ, "succ_n({n}) of {period:?}");
+        assert_eq!(period.pred_n(n), pred, "pred_n({n}) of {period:?}");
+        assert_eq!(succ.pred_n(n), period, "pred_n({n}) of {succ:?}");
+        assert_eq!(pred.succ_n(n), period, "succ_n({n}) of {pred:?}");
+
+        let n = i64::try_from(n).expect("small");
+        assert_eq!(succ.to_monotonic(), index + n, "index of {succ:?}");
+        assert_eq!(pred.to_monotonic(), index - n, "index of {pred:?}");
+        assert_eq!(period.between(succ), n, "between {period:?} and {succ:?}");
+        assert_eq!(period.between(pred), -n, "between {period:?} and {pred:?}");
+
+        assert!(prev_succ < succ, "{prev_succ:?} isn't less than {succ:?}");
+        assert!(pred < prev_pred, "{pred:?} isn't less than {prev_pred:?}");
+        assert!(
+            prev_succ.start_datetime() < succ.start_datetime(),
+            "{succ:?} doesn't start after {prev_succ:?}"
+        );
+        assert!(
+            pred.start_datetime() < prev_pred.start_datetime(),
+            "{prev_pred:?} doesn't start after {pred:?}"
+        );
+    }
+}
+
+/// Run `check_period` for each of the `periods`
+pub fn check_periods<P>(periods: impl IntoIterator<Item = P>)
+where
+    P: TimeResolution + FromMonotonic + fmt::Debug,
+{
+    periods.into_iter().for_each(check_period);
+}
+
+/// Check that each of the `periods` is found from each date within it by `from_date`, and that
+/// consecutive periods are contiguous
+pub fn check_dates<P>(periods: impl IntoIterator<Item = P>)
+where
+    P: DateResolution + fmt::Debug,
+{
+    for period in periods {
+        let (start, next) = (period.start(), period.succ().start());
+        assert!(start < next, "{period:?} has no dates");
+        for date in start.iter_days().take_while(|date| *date < next) {
+            assert_eq!(
+                P::from_date(date, period.params()),
+                period,
+                "from_date({date}) isn't {period:?}"
+            );
+        }
+        assert_eq!(
+            period.start_datetime(),
+            start.and_time(chrono::NaiveTime::MIN).and_utc(),
+            "start_datetime of {period:?}"
+        );
+    }
+}
+
+/// Check that each range of `periods` of `Long` rescales to a range of `Short` covering the same
+/// time, which rescales back to the original range
+pub fn check_rescale<Long, Short>(periods: impl IntoIterator<Item = Long>)
+where
+    Long: TimeResolution + From<DateTime<Utc>> + Rescalable<Short> + fmt::Debug,
+    Short: TimeResolution + From<DateTime<Utc>> + Rescalable<Long> + fmt::Debug,
+{
+    for period in periods {
+        let range = TimeRange::from_bounds(period, period.succ_n(STEPS));
+        let rescaled = range.rescale::<Short>();
+        assert_eq!(
+            rescaled.start().start_datetime(),
+            range.start().start_datetime(),
+            "start of {range:?} rescaled to {rescaled:?}"
+        );
+        assert_eq!(
+            rescaled.end().succ().start_datetime(),
+            range.end().succ().start_datetime(),
+            "end of {range:?} rescaled to {rescaled:?}"
+        );
+        assert_eq!(
+            rescaled.rescale::<Long>(),
+            range,
+            "{range:?} rescaled to {rescaled:?} and back"
+        );
+    }
+}
+
+/// Check that each of the `values` is parsed from its `Display` output
+pub fn check_display_parse<T>(values: impl IntoIterator<Item = T>)
+where
+    T: fmt::Display + str::FromStr + PartialEq + fmt::Debug,
+    T::Err: fmt::Debug,
+{
+    for value in values {
+        let displayed = value.to_string();
+        match displayed.parse::<T>() {
+            Ok(parsed) => assert_eq!(parsed, value, "{value:?} displayed as {displayed}"),
+            Err(e) => panic!("{value:?} displayed as {displayed} couldn't be parsed: {e:?}"),
+        }
+    }
+}
+
+/// Check that each of the `values` is deserialized from its serialized form, in JSON
+#[cfg(all(feature = "serde", feature = "serde_json"))]
+pub fn check_serde<T>(values: impl IntoIterator<Item = T>)
+where
+    T: serde::Serialize + serde::de::DeserializeOwned + PartialEq + fmt::Debug,
+{
+    for value in values {
+        let serialized = serde_json::to_string(&value)
+            .unwrap_or_else(|e| panic!("{value:?} couldn't be serialized: {e}"));
+        match serde_json::from_str::<T>(&serialized) {
+            Ok(deserialized) => {
+                assert_eq!(deserialized, value, "{value:?} serialized as {serialized}")
+            }
+            Err(e) => panic!("{value:?} serialized as {serialized} couldn't be deserialized: {e}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Day, FiscalQuarter, FiscalYear, FiveMinute, HalfHour, HalfYear, Hour, IsoWeek, July,
+        Millis, Minute, Minutes, Monday, Month, Quarter, Second, Seconds, Sunday, Sydney,
+        TypedResolution, Week, Year, Zoned,
+    };
+
+    macro_rules! conformance {
+        ($($res:ty),+ $(,)?) => {$(
+            check_periods(sample_periods(<$res>::containing));
+            check_display_parse(sample_periods(<$res>::containing));
+            #[cfg(all(feature = "serde", feature = "serde_json"))]
+            check_serde(sample_periods(<$res>::containing));
+        )+};
+    }
+
+    #[test]
+    fn test_sub_date() {
+        conformance!(
+            Millis<250>,
+            Second,
+            Seconds<15>,
+            Minute,
+            FiveMinute,
+            Minutes<20>,
+            HalfHour,
+            Hour,
+            Minutes<240>,
+        );
+        check_rescale::<Hour, FiveMinute>(sample_periods(Hour::containing));
+        check_rescale::<Minutes<240>, Minutes<20>>(sample_periods(Minutes::<240>::containing));
+        check_rescale::<Second, Millis<250>>(sample_periods(Second::containing));
+        check_rescale::<Day, HalfHour>(sample_periods(Day::containing));
+
+        // resolutions without a `TypedResolution` impl can be sampled with their own constructor
+        check_periods(sample_periods(|datetime| {
+            Zoned::<Hour, Utc>::from(datetime)
+        }));
+        // including in a zone with daylight savings, where some local periods are repeated or
+        // skipped
+        check_periods(sample_periods(|datetime| {
+            Zoned::<HalfHour, Sydney>::from(datetime.with_timezone(&Sydney))
+        }));
+        check_periods(sample_periods(|datetime| {
+            Zoned::<Hour, Sydney>::from(datetime.with_timezone(&Sydney))
+        }));
+    }
+
+    #[test]
+    fn test_date() {
+        conformance!(
+            Day,
+            Week<Monday>,
+            Week<Sunday>,
+            IsoWeek,
+            Month,
+            Quarter,
+            HalfYear,
+            Year,
+            FiscalQuarter<July>,
+            FiscalYear<July>,
+        );
+        check_dates(sample_periods(Day::containing));
+        check_dates(sample_periods(Week::<Sunday>::containing));
+        check_dates(sample_periods(IsoWeek::containing));
+        check_dates(sample_periods(Month::containing));
+        check_dates(sample_periods(Quarter::containing));
+        check_dates(sample_periods(HalfYear::containing));
+        check_dates(sample_periods(Year::containing));
+        check_dates(sample_periods(FiscalQuarter::<July>::containing));
+        check_dates(sample_periods(FiscalYear::<July>::containing));
+        check_rescale::<Month, Day>(sample_periods(Month::containing));
+        check_rescale::<Year, Month>(sample_periods(Year::containing));
+        check_rescale::<FiscalYear<July>, Month>(sample_periods(FiscalYear::<July>::containing));
+    }
+
+    #[test]
+    #[should_panic(expected = "displayed as")]
+    fn test_failure() {
+        #[derive(Debug, PartialEq)]
+        struct Lossy(Day);
+
+        impl fmt::Display for Lossy {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}", self.0.month())
+            }
+        }
+
+        impl str::FromStr for Lossy {
+            type Err = crate::Error;
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Ok(Lossy(s.parse::<crate::Month>()?.first_day()))
+            }
+        }
+
+        check_display_parse(sample_periods(Day::containing).map(Lossy));
+    }
+}
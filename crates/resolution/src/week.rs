@@ -1,7 +1,8 @@
-use alloc::{fmt, str};
+#[cfg(feature = "serde")]
 use alloc::{format, string::String};
 use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, Utc};
 use core::marker;
+use core::{fmt, str};
 
 use crate::{DateResolution, FromMonotonic};
 
@@ -190,7 +191,7 @@ impl<D: StartDay> DateResolution for Week<D> {
     fn params(&self) -> Self::Params {}
 
     fn from_date(date: NaiveDate, _params: Self::Params) -> Self {
-        let week_num = (date - base(D::weekday())).num_days() / 7;
+        let week_num = (date - base(D::weekday())).num_days().div_euclid(7);
 
         Week::from_monotonic(week_num)
     }
@@ -208,8 +209,8 @@ impl<D: StartDay> crate::TimeResolution for Week<D> {
             .and_time(NaiveTime::MIN)
             .and_utc()
     }
-    fn name(&self) -> String {
-        format!("Week[StartDay:{}]", D::NAME)
+    fn fmt_name(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Week[StartDay:{}]", D::NAME)
     }
 }
 
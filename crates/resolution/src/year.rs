@@ -1,5 +1,4 @@
-use crate::{month, DateResolution, DateResolutionExt, Quarter};
-use alloc::string::{String, ToString};
+use crate::{month, DateResolution, DateResolutionExt, HalfYear, Quarter};
 use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, Utc};
 use core::{convert::TryFrom, fmt, str};
 
@@ -38,8 +37,8 @@ impl crate::TimeResolution for Year {
         self.start().and_time(NaiveTime::MIN).and_utc()
     }
 
-    fn name(&self) -> String {
-        "Year".to_string()
+    fn fmt_name(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Year")
     }
 }
 
@@ -77,6 +76,12 @@ impl Year {
     pub fn last_quarter(&self) -> Quarter {
         self.end().into()
     }
+    pub fn first_half(&self) -> HalfYear {
+        self.start().into()
+    }
+    pub fn last_half(&self) -> HalfYear {
+        self.end().into()
+    }
     pub fn year_num(&self) -> i16 {
         i16::try_from(self.0).expect("Not pre/post historic")
     }
@@ -94,7 +99,15 @@ impl fmt::Display for Year {
 impl str::FromStr for Year {
     type Err = crate::Error;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Year(s.parse()?))
+        let year = s.parse()?;
+        // only years which `NaiveDate` can hold have a start date
+        if NaiveDate::from_ymd_opt(year, 1, 1).is_none() {
+            return Err(crate::Error::ParseCustom {
+                ty_name: "Year",
+                input: crate::error_text(s),
+            });
+        }
+        Ok(Year(i64::from(year)))
     }
 }
 
@@ -133,5 +146,12 @@ mod tests {
         );
 
         assert!("a2021".parse::<Year>().is_err(),);
+        assert!(matches!(
+            "999999".parse::<Year>(),
+            Err(crate::Error::ParseCustom {
+                ty_name: "Year",
+                ..
+            })
+        ));
     }
 }
@@ -1,12 +1,13 @@
 use crate::DateResolution;
 use crate::DateResolutionExt;
-use crate::LongerThan;
-use crate::LongerThanOrEqual;
+use crate::DynResolution;
+use crate::FromMonotonic;
+use crate::IsoResolution;
+use crate::Minutes;
 use crate::Monotonic;
 use crate::SubDateResolution;
 use crate::TimeResolution;
-use alloc::format;
-use alloc::string::String;
+use crate::TypedResolution;
 use chrono::DateTime;
 use chrono::FixedOffset;
 use chrono::NaiveDate;
@@ -16,6 +17,8 @@ use chrono::TimeDelta;
 use chrono::TimeZone;
 use chrono::Utc;
 use core::fmt;
+use core::num::NonZeroU64;
+#[cfg(feature = "serde")]
 use core::result;
 
 pub trait FixedTimeZone: TimeZone + Copy + fmt::Debug {
@@ -32,11 +35,23 @@ impl FixedTimeZone for Utc {
 /// offset and zone itself. This is intended to allow assertion that a given resolution is in a certain
 /// timezone and thus allow finding the start and end times of that resolution with their correct UTC offsets.
 ///
-/// warning: this should not be used for `SubDateResolution`s larger than `Minutes<60>` or equivalent. (Ideally
-/// this restriction will be removed later)
+/// Local resolutions shorter than a day are stepped through in UTC, so that in zones with daylight savings
+/// the periods on transition days are correct (eg there are 46 or 50 `HalfHour`s in a day) and each period
+/// carries the offset in effect at its start. Local resolutions of a day or longer are stepped through in local
+/// time, with the offset being the one in effect at the start of the local date.
+///
+/// The monotonic index of a local resolution shorter than a day is the index of the first period of the
+/// same length in UTC which starts within it, rather than the index of the local resolution, so in zones
+/// with a non-zero offset it differs from the index of the same local period. For example the `Hour`
+/// starting at 10:00 +10:00 on 2024-01-01 has the index of the `Hour` starting at 00:00 UTC that day.
+/// Previously the index of the local resolution was used, so stored indexes from before this change refer
+/// to different periods, see `CHANGELOG.md`.
 ///
-/// note: this works perfectly well with _fixed_ and _non-fixed_ timezones, but many implementations are only
-/// available for fixed timezones.
+/// warning: in zones with daylight savings, this should not be used for `SubDateResolution`s which don't evenly
+/// divide into the daylight savings shift (usually one hour), eg `Minutes<120>`.
+///
+/// note: this works perfectly well with _fixed_ and _non-fixed_ timezones, but some implementations, such
+/// as serde support, are only available for fixed timezones.
 pub struct Zoned<R, Z>
 where
     R: TimeResolution,
@@ -74,7 +89,7 @@ where
 }
 
 #[cfg(feature = "serde")]
-impl<'de, R, Z> serde::Serialize for Zoned<R, Z>
+impl<R, Z> serde::Serialize for Zoned<R, Z>
 where
     R: SubDateResolution<Params = ()>,
     Z: FixedTimeZone,
@@ -92,36 +107,85 @@ where
 impl<R, Z> TimeResolution for Zoned<R, Z>
 where
     R: TimeResolution,
-    Z: FixedTimeZone,
+    Z: TimeZone + Copy + fmt::Debug,
 {
     fn succ_n(&self, n: u64) -> Self {
-        Zoned {
-            local_resolution: self.local_resolution.succ_n(n),
-            ..*self
+        match self.sub_date_length() {
+            Some(length) => self.step_utc(length, i64::try_from(n).unwrap()),
+            None => Zoned::with_offset_at_start(self.local_resolution.succ_n(n), self.zone),
         }
     }
     fn pred_n(&self, n: u64) -> Self {
-        Zoned {
-            local_resolution: self.local_resolution.pred_n(n),
-            ..*self
+        match self.sub_date_length() {
+            Some(length) => self.step_utc(length, -i64::try_from(n).unwrap()),
+            None => Zoned::with_offset_at_start(self.local_resolution.pred_n(n), self.zone),
         }
     }
     fn start_datetime(&self) -> DateTime<Utc> {
         self.utc_start_datetime()
     }
-    fn name(&self) -> String {
-        format!("Zoned[{},{:?}]", self.local_resolution.name(), self.zone)
+    fn fmt_name(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Zoned[{},{:?}]",
+            self.local_resolution.display_name(),
+            self.zone
+        )
     }
 }
 
 impl<R, Z> Zoned<R, Z>
 where
     R: TimeResolution,
-    Z: FixedTimeZone,
+    Z: TimeZone + Copy + fmt::Debug,
 {
     pub fn local_end_exclusive(&self) -> chrono::DateTime<Z> {
         self.succ().local_start_datetime()
     }
+
+    /// The offset currently in effect at the start of this period
+    pub fn current_offset(&self) -> FixedOffset {
+        self.current_offset
+    }
+
+    // the length of the local resolution if it is shorter than a day
+    fn sub_date_length(&self) -> Option<TimeDelta> {
+        let length =
+            self.local_resolution.succ().start_datetime() - self.local_resolution.start_datetime();
+        (length < TimeDelta::days(1)).then_some(length)
+    }
+
+    // move by `n` periods of `length` in UTC, then find the local period at that time
+    fn step_utc(&self, length: TimeDelta, n: i64) -> Self {
+        let utc =
+            self.utc_start_datetime() + TimeDelta::milliseconds(length.num_milliseconds() * n);
+        let local = utc.with_timezone(&self.zone);
+        let local_delta = local.naive_local() - self.local_resolution.start_datetime().naive_utc();
+        let periods = local_delta
+            .num_milliseconds()
+            .div_euclid(length.num_milliseconds());
+        Zoned {
+            local_resolution: if periods >= 0 {
+                self.local_resolution.succ_n(periods.unsigned_abs())
+            } else {
+                self.local_resolution.pred_n(periods.unsigned_abs())
+            },
+            current_offset: local.offset().fix(),
+            zone: self.zone,
+        }
+    }
+
+    // for local resolutions of a day or longer, the offset is the one at the start of the local date
+    fn with_offset_at_start(local_resolution: R, zone: Z) -> Self {
+        Zoned {
+            local_resolution,
+            current_offset: local_offset_at_start_of_date(
+                local_resolution.start_datetime().date_naive(),
+                zone,
+            ),
+            zone,
+        }
+    }
 }
 
 impl<R, Z> Zoned<R, Z>
@@ -192,14 +256,65 @@ where
     Z: TimeZone + Copy + fmt::Debug,
     R: TimeResolution,
 {
+    // periods shorter than a day are indexed by their UTC start, as the local resolution
+    // can be repeated or skipped over a daylight savings transition. Where the offset isn't a
+    // whole number of periods, such as an `Hour` at +05:30, the start isn't a multiple of the
+    // length, so the index is rounded up to the first multiple within the period.
     fn to_monotonic(&self) -> i64 {
-        self.local_resolution.to_monotonic()
+        match self.sub_date_length() {
+            Some(length) => {
+                let start = self.utc_start_datetime().timestamp_millis();
+                let length = length.num_milliseconds();
+                start.div_euclid(length) + i64::from(start.rem_euclid(length) != 0)
+            }
+            None => self.local_resolution.to_monotonic(),
+        }
     }
     fn between(&self, other: Self) -> i64 {
         other.to_monotonic() - self.to_monotonic()
     }
 }
 
+// periods shorter than a day are indexed by a UTC instant within them, so the zone must be
+// known to find the local resolution
+impl<R, Z> FromMonotonic for Zoned<R, Z>
+where
+    R: SubDateResolution<Params = ()> + FromMonotonic,
+    Z: FixedTimeZone,
+{
+    fn from_monotonic(idx: i64) -> Self {
+        let first = R::from_monotonic(0);
+        let length = first.succ().start_datetime() - first.start_datetime();
+        let start = DateTime::from_timestamp_millis(idx * length.num_milliseconds())
+            .expect("valid datetime");
+        Self::from_utc_datetime(start, Z::new())
+    }
+}
+
+// sub-daily periods in zones with daylight savings can have the same local start, so `Zoned`
+// periods are written with their offset, and found from the UTC instant they start at
+impl<R, Z> IsoResolution for Zoned<R, Z>
+where
+    R: TypedResolution,
+    Z: FixedTimeZone,
+{
+    fn iso_resolution() -> DynResolution {
+        R::dyn_resolution()
+    }
+    fn iso_offset(&self) -> Option<FixedOffset> {
+        Some(self.current_offset)
+    }
+    fn from_iso_start(start: DateTime<Utc>) -> Option<Self> {
+        let local = start.with_timezone(&Z::new());
+        let period = Zoned {
+            local_resolution: R::containing(local.naive_local().and_utc()),
+            current_offset: local.offset().fix(),
+            zone: local.timezone(),
+        };
+        (period.utc_start_datetime() == start).then_some(period)
+    }
+}
+
 // impl<R1, R2, Z> LongerThan<Zoned<R2, Z>> for Zoned<R1, Z>
 // where
 //     R1: TimeResolution,
@@ -221,7 +336,7 @@ where
 impl<R, Z> SubDateResolution for Zoned<R, Z>
 where
     R: SubDateResolution<Params = ()>,
-    Z: FixedTimeZone,
+    Z: TimeZone + Copy + fmt::Debug,
 {
     type Params = Z;
     fn params(&self) -> Self::Params {
@@ -232,16 +347,8 @@ where
     }
 
     fn first_on_day(day: chrono::NaiveDate, params: Self::Params) -> Self {
-        // find the start time of the day in UTC!
-        // unwrap: should be ok, becuase empirically no recent TZ offset transitions at midnight
-        // however, these could theoretically happen.
-        let start_time_of_day = day
-            .and_time(NaiveTime::MIN)
-            .and_local_timezone(params)
-            .single()
-            .unwrap()
-            .to_utc();
-        Self::from_utc_datetime(start_time_of_day, params)
+        // find the start time of the day in UTC, allowing for the unusual case of an offset transition at midnight
+        Self::from_utc_datetime(local_start_of_date(day, params).to_utc(), params)
     }
 
     fn from_utc_datetime(datetime: DateTime<Utc>, params: Self::Params) -> Self {
@@ -252,7 +359,7 @@ where
 impl<R, Z> DateResolution for Zoned<R, Z>
 where
     R: DateResolution<Params = ()>,
-    Z: FixedTimeZone,
+    Z: TimeZone + Copy + fmt::Debug,
 {
     type Params = Z;
     fn params(&self) -> Self::Params {
@@ -289,6 +396,14 @@ where
 }
 
 fn local_offset_at_start_of_date<Z>(date: NaiveDate, tz: Z) -> FixedOffset
+where
+    Z: TimeZone + Copy,
+{
+    local_start_of_date(date, tz).offset().fix()
+}
+
+// the first valid local time on the date, which is midnight unless there is an offset transition at midnight
+fn local_start_of_date<Z>(date: NaiveDate, tz: Z) -> DateTime<Z>
 where
     Z: TimeZone + Copy,
 {
@@ -296,8 +411,7 @@ where
         .filter_map(|minutes_offset| {
             let local_start =
                 date.and_time(NaiveTime::MIN) + TimeDelta::try_minutes(minutes_offset)?;
-            let with_tz = local_start.and_local_timezone(tz).single()?;
-            Some(with_tz.offset().fix())
+            local_start.and_local_timezone(tz).earliest()
         })
         .next()
         // possible to panic, but _extremely_ unlikely
@@ -382,6 +496,37 @@ where
 {
 }
 
+/// Numbering of the periods within a local date, which unlike `DaySubdivison` allows for
+/// the number of periods changing on days with a daylight savings transition
+impl<const N: u32, Z> Zoned<Minutes<N>, Z>
+where
+    Z: TimeZone + Copy + fmt::Debug,
+{
+    /// The number of periods on the local date, eg 46, 48 or 50 `HalfHour`s in a zone with daylight savings
+    pub fn periods_on_date(date: NaiveDate, zone: Z) -> NonZeroU64 {
+        let first = Self::first_on_day(date, zone);
+        let next = Self::first_on_day(date.succ_opt().expect("valid date"), zone);
+        NonZeroU64::new(u64::try_from(first.between(next)).expect("next day is later"))
+            .expect("every day has at least one period")
+    }
+
+    /// The 1-based number of this period within its local date
+    pub fn period_number(&self) -> NonZeroU64 {
+        let first = Self::first_on_day(self.occurs_on_date(), self.zone);
+        NonZeroU64::new(u64::try_from(first.between(*self)).expect("first is earliest") + 1)
+            .expect("non-zero as 1 is added")
+    }
+
+    /// Find the period with the 1-based `period_no` on the local date, returns `None` if
+    /// the date has fewer periods.
+    pub fn from_period_number(date: NaiveDate, period_no: NonZeroU64, zone: Z) -> Option<Self> {
+        if period_no > Self::periods_on_date(date, zone) {
+            return None;
+        }
+        Some(Self::first_on_day(date, zone).succ_n(period_no.get() - 1))
+    }
+}
+
 impl<R, Z> Zoned<R, Z>
 where
     R: SubDateResolution,
@@ -394,11 +539,53 @@ mod tests {
     use crate::DateResolution;
     use crate::Day;
     use crate::FixedTimeZone;
+    use crate::FromMonotonic;
     use crate::Minutes;
+    use crate::Monotonic;
+    use crate::TimeResolution;
     use crate::Zoned;
     use alloc::vec::Vec;
     use chrono::FixedOffset;
 
+    #[derive(Debug, Clone, Copy)]
+    struct FixedEast<const N: i32>;
+
+    impl<const N: i32> chrono::TimeZone for FixedEast<N> {
+        type Offset = FixedOffset;
+
+        fn from_offset(_: &Self::Offset) -> Self {
+            Self
+        }
+
+        fn offset_from_local_date(
+            &self,
+            _: &chrono::prelude::NaiveDate,
+        ) -> chrono::MappedLocalTime<Self::Offset> {
+            unimplemented!()
+        }
+
+        fn offset_from_local_datetime(
+            &self,
+            _: &chrono::prelude::NaiveDateTime,
+        ) -> chrono::MappedLocalTime<Self::Offset> {
+            chrono::MappedLocalTime::Single(chrono::FixedOffset::east_opt(N).unwrap())
+        }
+
+        fn offset_from_utc_date(&self, _: &chrono::prelude::NaiveDate) -> Self::Offset {
+            unimplemented!()
+        }
+
+        fn offset_from_utc_datetime(&self, _: &chrono::prelude::NaiveDateTime) -> Self::Offset {
+            chrono::FixedOffset::east_opt(N).unwrap()
+        }
+    }
+
+    impl<const N: i32> FixedTimeZone for FixedEast<N> {
+        fn new() -> Self {
+            FixedEast
+        }
+    }
+
     #[test]
     fn test_subdate() {
         fn subdate<const N: u32>(tz: chrono_tz::Tz) {
@@ -466,6 +653,14 @@ mod tests {
                     Zoned::<Minutes<N>, _>::from(start_timestamp.clone())
                 );
 
+                let zoned = Zoned::<Minutes<N>, Z>::from(start_timestamp.clone());
+                assert_eq!(Zoned::from_monotonic(zoned.to_monotonic()), zoned);
+                assert_eq!(
+                    zoned.succ().to_monotonic(),
+                    zoned.to_monotonic() + 1,
+                    "{zoned:?}"
+                );
+
                 #[cfg(feature = "serde")]
                 assert_eq!(
                     serde_json::from_str::<Zoned::<Minutes<N>, _>>(
@@ -480,45 +675,6 @@ mod tests {
             }
         }
 
-        #[derive(Debug, Clone, Copy)]
-        struct FixedEast<const N: i32>;
-
-        impl<const N: i32> chrono::TimeZone for FixedEast<N> {
-            type Offset = FixedOffset;
-
-            fn from_offset(_: &Self::Offset) -> Self {
-                Self
-            }
-
-            fn offset_from_local_date(
-                &self,
-                _: &chrono::prelude::NaiveDate,
-            ) -> chrono::MappedLocalTime<Self::Offset> {
-                unimplemented!()
-            }
-
-            fn offset_from_local_datetime(
-                &self,
-                _: &chrono::prelude::NaiveDateTime,
-            ) -> chrono::MappedLocalTime<Self::Offset> {
-                chrono::MappedLocalTime::Single(chrono::FixedOffset::east_opt(N).unwrap())
-            }
-
-            fn offset_from_utc_date(&self, _: &chrono::prelude::NaiveDate) -> Self::Offset {
-                unimplemented!()
-            }
-
-            fn offset_from_utc_datetime(&self, _: &chrono::prelude::NaiveDateTime) -> Self::Offset {
-                chrono::FixedOffset::east_opt(N).unwrap()
-            }
-        }
-
-        impl<const N: i32> FixedTimeZone for FixedEast<N> {
-            fn new() -> Self {
-                FixedEast
-            }
-        }
-
         fn test_for_zone<F: FixedTimeZone>() {
             subdate_fixed::<1, _>(F::new());
             subdate_fixed::<2, _>(F::new());
@@ -536,6 +692,40 @@ mod tests {
         test_for_zone::<chrono::Utc>();
         test_for_zone::<FixedEast<{ 60 * 60 * 3 }>>();
         test_for_zone::<FixedEast<{ 60 * 60 * -4 }>>();
+        // offsets which aren't a whole number of hours
+        test_for_zone::<FixedEast<{ 60 * 60 * 5 + 60 * 30 }>>();
+        test_for_zone::<FixedEast<{ 60 * 60 * -9 - 60 * 30 }>>();
+    }
+
+    #[test]
+    fn test_monotonic_index() {
+        use crate::Hour;
+
+        let datetime = |h, m| crate::date(2024, 1, 1).and_hms_opt(h, m, 0).unwrap();
+
+        // sub-daily periods have the index of the first UTC period starting within them
+        let utc = Zoned::<Hour, chrono::Utc>::from(datetime(10, 0).and_utc());
+        assert_eq!(utc.to_monotonic(), 473_362);
+        let east = Zoned::<Hour, FixedEast<{ 60 * 60 * 10 }>>::from(
+            datetime(10, 0)
+                .and_local_timezone(FixedEast::<{ 60 * 60 * 10 }>)
+                .unwrap(),
+        );
+        assert_eq!(east.to_monotonic(), 473_352);
+        let india = Zoned::<Hour, FixedEast<{ 60 * 60 * 5 + 60 * 30 }>>::from(
+            datetime(10, 0)
+                .and_local_timezone(FixedEast::<{ 60 * 60 * 5 + 60 * 30 }>)
+                .unwrap(),
+        );
+        assert_eq!(india.to_monotonic(), 473_357);
+
+        // while longer periods have the index of their local resolution
+        let day =
+            Zoned::<Day, _>::from_date(crate::date(2024, 1, 1), FixedEast::<{ 60 * 60 * 10 }>);
+        assert_eq!(
+            day.to_monotonic(),
+            Day::new(crate::date(2024, 1, 1)).to_monotonic()
+        );
     }
 
     #[test]
@@ -570,4 +760,147 @@ mod tests {
             date::<Day>(tz);
         }
     }
+
+    #[test]
+    fn test_dst() {
+        use crate::{HalfHour, SubDateResolution, TimeRange, TimeResolution};
+
+        fn check_day(date: chrono::NaiveDate, periods: u64, offsets: &[(i32, u64)]) {
+            let tz = chrono_tz::Australia::Sydney;
+            let day = TimeRange::from_bounds(
+                Zoned::<HalfHour, _>::first_on_day(date, tz),
+                Zoned::<HalfHour, _>::last_on_day(date, tz),
+            );
+            assert_eq!(day.len().get(), periods);
+            assert_eq!(day.iter().count(), usize::try_from(periods).unwrap());
+            assert_eq!(
+                day.start().local_start_datetime().time(),
+                chrono::NaiveTime::MIN
+            );
+            assert_eq!(
+                day.end().local_end_exclusive(),
+                Zoned::<HalfHour, _>::first_on_day(date.succ_opt().unwrap(), tz)
+                    .local_start_datetime()
+            );
+
+            let mut expected_offsets = offsets
+                .iter()
+                .flat_map(|(hours, count)| (0..*count).map(move |_| hours * 60 * 60));
+            for (period, next) in day.iter().zip(day.iter().skip(1)) {
+                assert_eq!(
+                    next.utc_start_datetime() - period.utc_start_datetime(),
+                    chrono::Duration::minutes(30)
+                );
+                assert_eq!(period.succ(), next);
+                assert_eq!(next.pred(), period);
+                assert_eq!(period.occurs_on_date(), date);
+                assert_eq!(
+                    Some(period.current_offset().local_minus_utc()),
+                    expected_offsets.next()
+                );
+            }
+            assert_eq!(
+                day.start().succ_n(periods - 1).pred_n(periods - 1),
+                day.start()
+            );
+            assert_eq!(day.start().succ_n(periods), day.end().succ());
+        }
+
+        // daylight savings ends, 02:00 - 03:00 is repeated
+        check_day(
+            chrono::NaiveDate::from_ymd_opt(2024, 4, 7).unwrap(),
+            50,
+            &[(11, 6), (10, 44)],
+        );
+        // daylight savings starts, 02:00 - 03:00 is skipped
+        check_day(
+            chrono::NaiveDate::from_ymd_opt(2024, 10, 6).unwrap(),
+            46,
+            &[(10, 4), (11, 42)],
+        );
+        check_day(
+            chrono::NaiveDate::from_ymd_opt(2024, 7, 1).unwrap(),
+            48,
+            &[(10, 48)],
+        );
+
+        // days step in local time, carrying the offset at the start of the day
+        let day = Zoned::<Day, _>::from_date(
+            chrono::NaiveDate::from_ymd_opt(2024, 10, 5).unwrap(),
+            chrono_tz::Australia::Sydney,
+        );
+        assert_eq!(day.current_offset().local_minus_utc(), 10 * 60 * 60);
+        assert_eq!(day.succ().current_offset().local_minus_utc(), 10 * 60 * 60);
+        assert_eq!(
+            day.succ_n(2).current_offset().local_minus_utc(),
+            11 * 60 * 60
+        );
+        assert_eq!(
+            day.succ_n(2).local_start_datetime().time(),
+            chrono::NaiveTime::MIN
+        );
+    }
+
+    #[test]
+    fn test_period_number() {
+        use crate::HalfHour;
+        use core::num::NonZeroU64;
+
+        let tz = chrono_tz::Australia::Sydney;
+        for (date, periods) in [
+            (chrono::NaiveDate::from_ymd_opt(2024, 4, 7).unwrap(), 50),
+            (chrono::NaiveDate::from_ymd_opt(2024, 7, 1).unwrap(), 48),
+            (chrono::NaiveDate::from_ymd_opt(2024, 10, 6).unwrap(), 46),
+        ] {
+            assert_eq!(
+                Zoned::<HalfHour, _>::periods_on_date(date, tz).get(),
+                periods
+            );
+            for i in 1..=periods {
+                let period_no = NonZeroU64::new(i).unwrap();
+                let period = Zoned::<HalfHour, _>::from_period_number(date, period_no, tz).unwrap();
+                assert_eq!(period.period_number(), period_no);
+                assert_eq!(period.local_start_datetime().date_naive(), date);
+            }
+            assert!(Zoned::<HalfHour, _>::from_period_number(
+                date,
+                NonZeroU64::new(periods + 1).unwrap(),
+                tz
+            )
+            .is_none());
+        }
+
+        // the repeated hour when daylight savings ends
+        let date = chrono::NaiveDate::from_ymd_opt(2024, 4, 7).unwrap();
+        let first_two =
+            Zoned::<HalfHour, _>::from_period_number(date, NonZeroU64::new(5).unwrap(), tz)
+                .unwrap();
+        let second_two =
+            Zoned::<HalfHour, _>::from_period_number(date, NonZeroU64::new(7).unwrap(), tz)
+                .unwrap();
+        assert_eq!(
+            first_two.local_start_datetime().naive_local(),
+            second_two.local_start_datetime().naive_local()
+        );
+        assert_ne!(first_two, second_two);
+        assert_eq!(
+            Zoned::<HalfHour, _>::from_period_number(date, NonZeroU64::new(47).unwrap(), tz)
+                .unwrap()
+                .local_start_datetime()
+                .time(),
+            chrono::NaiveTime::from_hms_opt(22, 0, 0).unwrap()
+        );
+
+        // fixed zones match `DaySubdivison`
+        let date = chrono::NaiveDate::from_ymd_opt(2024, 4, 7).unwrap();
+        for i in 1..=48 {
+            let period_no = NonZeroU64::new(i).unwrap();
+            let period =
+                Zoned::<HalfHour, _>::from_period_number(date, period_no, chrono::Utc).unwrap();
+            assert_eq!(
+                period.local_resolution().relative(),
+                crate::DaySubdivison::<30>::new(period_no).unwrap()
+            );
+        }
+    }
 }